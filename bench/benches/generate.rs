@@ -0,0 +1,22 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use schema2000::generate_hypothesis;
+use schema2000_bench::Generator;
+use std::hint::black_box;
+
+fn generate(c: &mut Criterion) {
+    let mut generator = Generator::new(2000);
+    let wide = generator.wide_object(5_000);
+    let deep = generator.deep_object(500);
+    let array_heavy = generator.array_heavy(10_000);
+
+    let mut group = c.benchmark_group("generate_hypothesis");
+    group.bench_function("wide", |b| b.iter(|| generate_hypothesis(black_box(&wide))));
+    group.bench_function("deep", |b| b.iter(|| generate_hypothesis(black_box(&deep))));
+    group.bench_function("array_heavy", |b| {
+        b.iter(|| generate_hypothesis(black_box(&array_heavy)));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, generate);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use schema2000::model::NodeType;
+use schema2000::{generate_hypothesis, merge_hypothesis, merge_node_type, SchemaHypothesis};
+use schema2000_bench::{events, Generator};
+use std::hint::black_box;
+
+fn merge_documents(c: &mut Criterion) {
+    let documents = events(1_000);
+    let hypotheses: Vec<SchemaHypothesis> = documents.iter().map(generate_hypothesis).collect();
+
+    let mut group = c.benchmark_group("merge_documents");
+    group.bench_function("observe", |b| {
+        b.iter(|| {
+            let mut hypothesis = generate_hypothesis(&documents[0]);
+            for document in &documents[1..] {
+                hypothesis.observe(black_box(document));
+            }
+            hypothesis
+        });
+    });
+    group.bench_function("merge_in", |b| {
+        b.iter_batched(
+            || hypotheses.clone(),
+            |hypotheses| {
+                let mut hypotheses = hypotheses.into_iter();
+                let mut hypothesis = hypotheses.next().unwrap();
+                for other in hypotheses {
+                    hypothesis.merge_in(other);
+                }
+                hypothesis
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("merge_hypothesis", |b| {
+        b.iter_batched(
+            || hypotheses.clone(),
+            |hypotheses| hypotheses.into_iter().reduce(merge_hypothesis),
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+fn merge_large_hypotheses(c: &mut Criterion) {
+    let pairs = [
+        (
+            "wide",
+            Generator::new(1).wide_object(5_000),
+            Generator::new(2).wide_object(5_000),
+        ),
+        (
+            "deep",
+            Generator::new(1).deep_object(500),
+            Generator::new(2).deep_object(500),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("merge_hypothesis");
+    for (name, a, b) in &pairs {
+        let a = generate_hypothesis(a);
+        let b = generate_hypothesis(b);
+        group.bench_function(*name, |bencher| {
+            bencher.iter_batched(
+                || (a.clone(), b.clone()),
+                |(a, b)| merge_hypothesis(a, b),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// Merges the item types of two arrays, which are unions of all kinds of node types.
+fn merge_any(c: &mut Criterion) {
+    let items = |seed| match generate_hypothesis(&Generator::new(seed).array_heavy(1_000)).root {
+        NodeType::Array(array) => *array.items.unwrap(),
+        _ => unreachable!(),
+    };
+    let a = items(1);
+    let b = items(2);
+    assert!(matches!(&a, NodeType::Any(any) if any.nodes.len() > 5));
+
+    c.bench_function("merge_any", |bencher| {
+        bencher.iter_batched(
+            || (a.clone(), b.clone()),
+            |(a, b)| merge_node_type(a, b),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group!(benches, merge_documents, merge_large_hypotheses, merge_any);
+criterion_main!(benches);
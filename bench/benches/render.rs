@@ -0,0 +1,18 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use schema2000::{generate_hypothesis, render_schema};
+use schema2000_bench::Generator;
+use std::hint::black_box;
+
+fn render(c: &mut Criterion) {
+    let mut generator = Generator::new(2000);
+    let wide = generate_hypothesis(&generator.wide_object(5_000));
+    let deep = generate_hypothesis(&generator.deep_object(500));
+
+    let mut group = c.benchmark_group("render_schema");
+    group.bench_function("wide", |b| b.iter(|| render_schema(black_box(&wide))));
+    group.bench_function("deep", |b| b.iter(|| render_schema(black_box(&deep))));
+    group.finish();
+}
+
+criterion_group!(benches, render);
+criterion_main!(benches);
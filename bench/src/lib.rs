@@ -0,0 +1,122 @@
+//! Synthetic JSON documents for the benchmarks. All documents are derived from a seed with a
+//! small built-in pseudo random number generator, so benchmark results are reproducible and do
+//! not depend on any downloaded data.
+
+use serde_json::{json, Map, Value};
+
+/// Documents of a typical event stream: mostly the same shape, with optional and differently
+/// typed properties in some of them.
+#[must_use]
+pub fn events(count: usize) -> Vec<Value> {
+    (0..count)
+        .map(|i| {
+            let mut event = json!({
+                "id": i,
+                "type": "order-created",
+                "timestamp": "2024-05-01T12:00:00Z",
+                "customer": {"id": format!("c{}", i % 100), "segment": i % 3},
+                "items": [
+                    {"sku": "4711", "quantity": 1, "price": 1.99},
+                    {"sku": "0815", "quantity": 2, "price": 0.5, "discount": 0.1}
+                ]
+            });
+            if i % 10 == 0 {
+                event["note"] = json!(null);
+            }
+            if i % 7 == 0 {
+                event["customer"]["segment"] = json!("unknown");
+            }
+            event
+        })
+        .collect()
+}
+
+/// Generates synthetic documents of different shapes from a seed (using SplitMix64).
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Generator { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A scalar value of a random type, including strings that are dates and date-times.
+    pub fn scalar(&mut self) -> Value {
+        match self.below(8) {
+            0 => Value::Null,
+            1 => json!(self.below(2) == 0),
+            2 => json!(self.below(1_000_000)),
+            3 => json!(self.next_u64() as f64 / u64::MAX as f64),
+            4 => json!(format!(
+                "2024-{:02}-{:02}",
+                self.below(12) + 1,
+                self.below(28) + 1
+            )),
+            5 => json!(format!("2024-05-01T{:02}:00:00Z", self.below(24))),
+            _ => json!(format!("text {}", self.below(1_000))),
+        }
+    }
+
+    /// An object with `properties` scalar properties, drawn from twice as many property names.
+    pub fn wide_object(&mut self, properties: usize) -> Value {
+        let mut map = Map::new();
+        for _ in 0..properties {
+            let name = format!("property_{}", self.below(properties * 2));
+            map.insert(name, self.scalar());
+        }
+        Value::Object(map)
+    }
+
+    /// Objects nested `depth` levels deep, with a few scalar properties on every level.
+    pub fn deep_object(&mut self, depth: usize) -> Value {
+        let mut value = self.wide_object(3);
+        for level in 0..depth {
+            let mut object = self.wide_object(3);
+            object[format!("level_{}", level % 3)] = value;
+            value = object;
+        }
+        value
+    }
+
+    /// An array of `length` items, mixing scalars, small objects and nested arrays.
+    pub fn array_heavy(&mut self, length: usize) -> Value {
+        let items = (0..length)
+            .map(|_| match self.below(4) {
+                0 => self.wide_object(4),
+                1 => Value::Array((0..self.below(5)).map(|_| self.scalar()).collect()),
+                _ => self.scalar(),
+            })
+            .collect();
+        Value::Array(items)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Generator;
+
+    #[test]
+    fn test_deterministic() {
+        let a = Generator::new(42).array_heavy(100);
+        let b = Generator::new(42).array_heavy(100);
+        let c = Generator::new(43).array_heavy(100);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+#[derive(Debug)]
+pub enum CliError {
+    /// An input or output file could not be opened.
+    Open { path: String, source: io::Error },
+    /// The input could not be read or contains invalid JSON.
+    Input {
+        input: Option<String>,
+        source: schema2000::Error,
+    },
+    /// The schema or an invalid record could not be written.
+    Write { target: String, source: io::Error },
+    /// The handler for termination signals could not be installed.
+    Signals(io::Error),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Open { path, source } => write!(f, "failed to open {path}: {source}"),
+            CliError::Input {
+                input: Some(input),
+                source,
+            } => write!(f, "{input}: {source}"),
+            CliError::Input {
+                input: None,
+                source,
+            } => write!(f, "{source}"),
+            CliError::Write { target, source } => {
+                write!(f, "failed to write to {target}: {source}")
+            }
+            CliError::Signals(source) => {
+                write!(f, "failed to install the signal handler: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CliError::Open { source, .. }
+            | CliError::Write { source, .. }
+            | CliError::Signals(source) => Some(source),
+            CliError::Input { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<schema2000::Error> for CliError {
+    fn from(source: schema2000::Error) -> Self {
+        CliError::Input {
+            input: None,
+            source,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use clap::ValueEnum;
+use schema2000::{
+    render_avro, render_bigquery, render_go, render_java, render_json_schema_with_options,
+    render_kotlin, render_openapi, render_protobuf, render_pydantic, render_rust, render_sql,
+    render_typescript, AvroOptions, BigQueryOptions, JsonSchemaOptions, OpenApiOptions,
+    OpenApiVersion, ProtobufOptions, SchemaHypothesis, SqlOptions,
+};
+use serde_json::Value;
+
+use crate::Args;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    JsonSchema,
+    #[value(name = "openapi-3.0")]
+    OpenApi3_0,
+    #[value(name = "openapi-3.1")]
+    OpenApi3_1,
+    #[value(name = "typescript")]
+    TypeScript,
+    Rust,
+    Avro,
+    Protobuf,
+    Go,
+    Pydantic,
+    Kotlin,
+    Java,
+    Sql,
+    #[value(name = "bigquery")]
+    BigQuery,
+}
+
+/// Renders `hypothesis` in the format selected by `args`.
+pub fn render(hypothesis: &SchemaHypothesis, args: &Args) -> String {
+    match args.format {
+        Format::JsonSchema => to_json(
+            &render_json_schema_with_options(hypothesis, &json_schema_options(args)),
+            args,
+        ),
+        Format::OpenApi3_0 => to_json(
+            &render_openapi(hypothesis, &openapi_options(args, OpenApiVersion::V3_0)),
+            args,
+        ),
+        Format::OpenApi3_1 => to_json(
+            &render_openapi(hypothesis, &openapi_options(args, OpenApiVersion::V3_1)),
+            args,
+        ),
+        Format::TypeScript => render_typescript(hypothesis, &args.name),
+        Format::Rust => render_rust(hypothesis, &args.name),
+        Format::Avro => {
+            let schema = render_avro(hypothesis, &avro_options(args));
+            for warning in &schema.warnings {
+                eprintln!("warning: {warning}");
+            }
+            to_json(&schema.schema, args)
+        }
+        Format::Protobuf => render_protobuf(hypothesis, &protobuf_options(args)),
+        Format::Go => render_go(hypothesis, &args.name, go_package(args)),
+        Format::Pydantic => render_pydantic(hypothesis, &args.name),
+        Format::Kotlin => render_kotlin(hypothesis, &args.name, args.namespace.as_deref()),
+        Format::Java => render_java(hypothesis, &args.name, args.namespace.as_deref()),
+        Format::Sql => render_sql(hypothesis, &sql_options(args)),
+        Format::BigQuery => {
+            let options = BigQueryOptions {
+                fallback: args.bigquery_fallback.into(),
+            };
+            let schema = render_bigquery(hypothesis, &options);
+            for warning in &schema.warnings {
+                eprintln!("warning: {warning}");
+            }
+            to_json(&schema.fields, args)
+        }
+    }
+}
+
+pub fn json_schema_options(args: &Args) -> JsonSchemaOptions {
+    JsonSchemaOptions {
+        draft: args.draft.map(Draft::into),
+        id: args.id.clone(),
+        title: args.title.clone(),
+        description: args.description.clone(),
+        closed: args.closed,
+        open_paths: args.open.iter().cloned().collect(),
+    }
+}
+
+fn openapi_options(args: &Args, version: OpenApiVersion) -> OpenApiOptions {
+    OpenApiOptions {
+        version,
+        root_name: args.name.clone(),
+        title: args.title.clone(),
+    }
+}
+
+fn avro_options(args: &Args) -> AvroOptions {
+    AvroOptions {
+        root_name: args.name.clone(),
+        namespace: args.namespace.clone(),
+        map_paths: args.map.iter().cloned().collect(),
+    }
+}
+
+fn protobuf_options(args: &Args) -> ProtobufOptions {
+    ProtobufOptions {
+        root_name: args.name.clone(),
+        package: args.namespace.clone(),
+        field_numbers: args.field_number.iter().cloned().collect(),
+    }
+}
+
+fn sql_options(args: &Args) -> SqlOptions {
+    SqlOptions {
+        table_name: args.name.clone(),
+        dialect: args.dialect.into(),
+        separator: args.separator.clone(),
+    }
+}
+
+/// The last segment of the namespace, e.g. `shop` for `example.com/shop`.
+fn go_package(args: &Args) -> &str {
+    args.namespace
+        .as_deref()
+        .and_then(|namespace| namespace.rsplit(['.', '/']).next())
+        .filter(|package| !package.is_empty())
+        .unwrap_or("model")
+}
+
+/// Parses a field number override like `Order.orderId=7`.
+pub fn parse_field_number(text: &str) -> Result<(String, u32), String> {
+    let (field, number) = text
+        .rsplit_once('=')
+        .ok_or_else(|| "expected MESSAGE.PROPERTY=N".to_string())?;
+    match number.parse() {
+        Ok(number) if (19_000..=19_999).contains(&number) => Err(format!(
+            "field number `{number}` is reserved for the implementation of Protocol Buffers"
+        )),
+        Ok(number) if (1..=536_870_911).contains(&number) => Ok((field.to_string(), number)),
+        _ => Err(format!("invalid field number `{number}`")),
+    }
+}
+
+/// Checks that no field is given twice and that no number is given to two fields of the same
+/// message.
+pub fn check_field_numbers(field_numbers: &[(String, u32)]) -> Result<(), String> {
+    let mut fields = BTreeSet::new();
+    let mut numbers = BTreeMap::new();
+    for (field, number) in field_numbers {
+        if !fields.insert(field) {
+            return Err(format!("`{field}` is given more than one field number"));
+        }
+        // message names cannot contain a dot, but keys can
+        let message = field
+            .split_once('.')
+            .map_or(field.as_str(), |(message, _)| message);
+        if let Some(other) = numbers.insert((message, *number), field) {
+            return Err(format!(
+                "`{other}` and `{field}` are both given the field number `{number}`"
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn to_json(value: &Value, args: &Args) -> String {
+    if args.compact {
+        value.to_string()
+    } else {
+        serde_json::to_string_pretty(value).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Draft {
+    #[value(name = "draft-04")]
+    Draft4,
+    #[value(name = "draft-06")]
+    Draft6,
+    #[value(name = "draft-07")]
+    Draft7,
+    #[value(name = "2019-09")]
+    Draft2019_09,
+    #[value(name = "2020-12")]
+    Draft2020_12,
+}
+
+impl From<Draft> for schema2000::Draft {
+    fn from(draft: Draft) -> Self {
+        match draft {
+            Draft::Draft4 => schema2000::Draft::Draft4,
+            Draft::Draft6 => schema2000::Draft::Draft6,
+            Draft::Draft7 => schema2000::Draft::Draft7,
+            Draft::Draft2019_09 => schema2000::Draft::Draft2019_09,
+            Draft::Draft2020_12 => schema2000::Draft::Draft2020_12,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum SqlDialect {
+    Postgres,
+    #[value(name = "mysql")]
+    MySql,
+    Sqlite,
+}
+
+impl From<SqlDialect> for schema2000::SqlDialect {
+    fn from(dialect: SqlDialect) -> Self {
+        match dialect {
+            SqlDialect::Postgres => schema2000::SqlDialect::Postgres,
+            SqlDialect::MySql => schema2000::SqlDialect::MySql,
+            SqlDialect::Sqlite => schema2000::SqlDialect::Sqlite,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum BigQueryFallback {
+    Json,
+    String,
+}
+
+impl From<BigQueryFallback> for schema2000::BigQueryFallback {
+    fn from(fallback: BigQueryFallback) -> Self {
+        match fallback {
+            BigQueryFallback::Json => schema2000::BigQueryFallback::Json,
+            BigQueryFallback::String => schema2000::BigQueryFallback::String,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::format::{check_field_numbers, parse_field_number};
+
+    #[test]
+    fn test_parse_field_number() {
+        assert_eq!(
+            parse_field_number("Order.orderId=7"),
+            Ok(("Order.orderId".to_string(), 7))
+        );
+        assert!(parse_field_number("Order.orderId").is_err());
+        assert!(parse_field_number("Order.orderId=0").is_err());
+        assert!(parse_field_number("Order.orderId=x").is_err());
+        assert!(parse_field_number("Order.orderId=19000").is_err());
+        assert!(parse_field_number("Order.orderId=19999").is_err());
+        assert!(parse_field_number("Order.orderId=20000").is_ok());
+    }
+
+    #[test]
+    fn test_check_field_numbers() {
+        let field_numbers = |fields: &[(&str, u32)]| -> Vec<(String, u32)> {
+            fields
+                .iter()
+                .map(|(field, number)| ((*field).to_string(), *number))
+                .collect()
+        };
+
+        assert_eq!(
+            check_field_numbers(&field_numbers(&[("Order.id", 1), ("Customer.id", 1)])),
+            Ok(())
+        );
+        assert_eq!(
+            check_field_numbers(&field_numbers(&[("Order.id", 1), ("Order.a.b", 1)])),
+            Err("`Order.id` and `Order.a.b` are both given the field number `1`".to_string())
+        );
+        assert_eq!(
+            check_field_numbers(&field_numbers(&[("Order.id", 1), ("Order.id", 2)])),
+            Err("`Order.id` is given more than one field number".to_string())
+        );
+    }
+}
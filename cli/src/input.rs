@@ -0,0 +1,354 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::error::CliError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "stdin"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Input {
+    /// Opens the input; compressed input is decompressed transparently.
+    pub fn open(&self) -> Result<Box<dyn Read + Send>, CliError> {
+        let open_error = |source| CliError::Open {
+            path: self.to_string(),
+            source,
+        };
+        let reader: Box<dyn Read + Send> = match self {
+            Input::Stdin => Box::new(io::stdin()),
+            Input::File(path) => Box::new(File::open(path).map_err(open_error)?),
+        };
+        decompress(reader).map_err(open_error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    const ALL: [Compression; 4] = [
+        Compression::Gzip,
+        Compression::Zstd,
+        Compression::Bzip2,
+        Compression::Xz,
+    ];
+
+    /// The bytes a compressed input starts with; JSON never starts with any of them.
+    fn magic(self) -> &'static [u8] {
+        match self {
+            Compression::Gzip => &[0x1f, 0x8b],
+            Compression::Zstd => &[0x28, 0xb5, 0x2f, 0xfd],
+            Compression::Bzip2 => b"BZh",
+            Compression::Xz => &[0xfd, b'7', b'z', b'X', b'Z', 0x00],
+        }
+    }
+
+    /// Detects the compression from the magic bytes at the start of the input, regardless of any
+    /// file extension.
+    fn detect(start: &[u8]) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|compression| start.starts_with(compression.magic()))
+    }
+
+    /// Whether more bytes are needed to tell if the input starting with `start` is compressed.
+    fn undecided(start: &[u8]) -> bool {
+        Self::ALL.iter().any(|compression| {
+            let magic = compression.magic();
+            start.len() < magic.len() && magic.starts_with(start)
+        })
+    }
+}
+
+fn decompress(mut reader: Box<dyn Read + Send>) -> io::Result<Box<dyn Read + Send>> {
+    // bytes are only read as long as they could be the start of a magic number, so a slow pipe
+    // delivering a single short document is not blocked waiting for more, while magic numbers
+    // split across reads are still detected; stdin can not be rewound, so the bytes read are put
+    // in front of the remaining input
+    let mut start = [0; 6];
+    let mut length = 0;
+    while Compression::undecided(&start[..length]) {
+        match reader.read(&mut start[length..]) {
+            Ok(0) => break,
+            Ok(read) => length += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    let compression = Compression::detect(&start[..length]);
+    let reader = io::Cursor::new(start[..length].to_vec()).chain(reader);
+
+    Ok(match compression {
+        None => Box::new(reader),
+        Some(Compression::Gzip) => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+        Some(Compression::Zstd) => Box::new(zstd::stream::read::Decoder::new(reader)?),
+        Some(Compression::Bzip2) => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+        Some(Compression::Xz) => Box::new(xz2::read::XzDecoder::new_multi_decoder(reader)),
+    })
+}
+
+/// Resolves the given paths to the files to read: directories are searched recursively (skipping
+/// hidden entries and directories visited before, e.g. via symlinks), glob patterns are expanded
+/// and `-` stands for stdin. Without any paths, stdin is read. Inputs given more than once are
+/// read once.
+pub fn expand(paths: &[String]) -> Result<Vec<Input>, CliError> {
+    if paths.is_empty() {
+        return Ok(vec![Input::Stdin]);
+    }
+
+    let mut inputs = Vec::new();
+    let mut visited = BTreeSet::new();
+    for path in paths {
+        if path == "-" {
+            inputs.push(Input::Stdin);
+        } else if Path::new(path).exists() || !is_glob_pattern(path) {
+            collect_files(Path::new(path), &mut visited, &mut inputs)?;
+        } else {
+            let matches = glob::glob(path).map_err(|error| CliError::Open {
+                path: path.clone(),
+                source: io::Error::new(io::ErrorKind::InvalidInput, error.msg),
+            })?;
+            let inputs_before = inputs.len();
+            for entry in matches {
+                let entry = entry.map_err(|error| CliError::Open {
+                    path: error.path().display().to_string(),
+                    source: error.into(),
+                })?;
+                collect_files(&entry, &mut visited, &mut inputs)?;
+            }
+            if inputs.len() == inputs_before {
+                return Err(CliError::Open {
+                    path: path.clone(),
+                    source: io::Error::new(io::ErrorKind::NotFound, "no file matches the pattern"),
+                });
+            }
+        }
+    }
+
+    let mut unique = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        if !unique.contains(&input) {
+            unique.push(input);
+        }
+    }
+    Ok(unique)
+}
+
+fn is_glob_pattern(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Collects the files at `path`; `visited` holds the canonical paths of the directories searched
+/// already, so symlinks pointing back to a parent directory do not lead to an endless recursion.
+fn collect_files(
+    path: &Path,
+    visited: &mut BTreeSet<PathBuf>,
+    inputs: &mut Vec<Input>,
+) -> Result<(), CliError> {
+    let open_error = |source| CliError::Open {
+        path: path.display().to_string(),
+        source,
+    };
+
+    if !fs::metadata(path).map_err(open_error)?.is_dir() {
+        inputs.push(Input::File(path.to_path_buf()));
+        return Ok(());
+    }
+    if !visited.insert(fs::canonicalize(path).map_err(open_error)?) {
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(open_error)?;
+    entries.sort();
+    for entry in entries {
+        let hidden = entry
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if !hidden {
+            collect_files(&entry, visited, inputs)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::io::Read;
+
+    use tempfile::TempDir;
+
+    use crate::input::{decompress, expand, Input};
+
+    fn create_files(files: &[&str]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "{}").unwrap();
+        }
+        dir
+    }
+
+    fn decompressed(input: Vec<u8>) -> String {
+        let mut result = String::new();
+        decompress(Box::new(std::io::Cursor::new(input)))
+            .unwrap()
+            .read_to_string(&mut result)
+            .unwrap();
+        result
+    }
+
+    /// Returns a single byte per read, like a slow pipe.
+    struct Trickle(std::io::Cursor<Vec<u8>>);
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let length = buf.len().min(1);
+            self.0.read(&mut buf[..length])
+        }
+    }
+
+    #[test]
+    fn test_decompress() {
+        let content = "{\"id\": 1}\n{\"id\": 2}\n";
+
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut gzip, content.as_bytes()).unwrap();
+        let zstd = zstd::encode_all(content.as_bytes(), 0).unwrap();
+        let mut bzip2 = Vec::new();
+        bzip2::read::BzEncoder::new(content.as_bytes(), bzip2::Compression::default())
+            .read_to_end(&mut bzip2)
+            .unwrap();
+        let mut xz = Vec::new();
+        xz2::read::XzEncoder::new(content.as_bytes(), 6)
+            .read_to_end(&mut xz)
+            .unwrap();
+
+        assert_eq!(decompressed(gzip.finish().unwrap()), content);
+        assert_eq!(decompressed(zstd), content);
+        assert_eq!(decompressed(bzip2), content);
+        assert_eq!(decompressed(xz), content);
+        assert_eq!(decompressed(content.as_bytes().to_vec()), content);
+        assert_eq!(decompressed(b"1".to_vec()), "1");
+    }
+
+    #[test]
+    fn test_decompress_split_magic() {
+        let content = "{\"id\": 1}\n";
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut gzip, content.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+
+        for input in [gzip, content.as_bytes().to_vec(), b"B".to_vec()] {
+            let mut expected = Vec::new();
+            decompress(Box::new(std::io::Cursor::new(input.clone())))
+                .unwrap()
+                .read_to_end(&mut expected)
+                .unwrap();
+            let mut actual = Vec::new();
+            decompress(Box::new(Trickle(std::io::Cursor::new(input))))
+                .unwrap()
+                .read_to_end(&mut actual)
+                .unwrap();
+
+            assert_eq!(actual, expected);
+        }
+        assert_eq!(decompressed(b"B".to_vec()), "B");
+    }
+
+    #[test]
+    fn test_stdin_by_default() {
+        assert_eq!(expand(&[]).unwrap(), vec![Input::Stdin]);
+        assert_eq!(expand(&["-".to_string()]).unwrap(), vec![Input::Stdin]);
+    }
+
+    #[test]
+    fn test_directory_recursively() {
+        let dir = create_files(&["b.json", "a/c.json", ".hidden/d.json"]);
+        let dir = dir.path();
+
+        let actual = expand(&[dir.display().to_string()]).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                Input::File(dir.join("a/c.json")),
+                Input::File(dir.join("b.json"))
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_cycle() {
+        let dir = create_files(&["a/b.json"]);
+        let dir = dir.path();
+        std::os::unix::fs::symlink("..", dir.join("a/loop")).unwrap();
+
+        let actual = expand(&[dir.display().to_string()]).unwrap();
+
+        assert_eq!(actual, vec![Input::File(dir.join("a/b.json"))]);
+    }
+
+    #[test]
+    fn test_inputs_given_twice() {
+        let dir = create_files(&["a.json", "b.json"]);
+        let a = dir.path().join("a.json").display().to_string();
+        let b = dir.path().join("b.json").display().to_string();
+
+        let actual = expand(&[a.clone(), b, a, "-".to_string(), "-".to_string()]).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                Input::File(dir.path().join("a.json")),
+                Input::File(dir.path().join("b.json")),
+                Input::Stdin
+            ]
+        );
+    }
+
+    #[test]
+    fn test_glob_pattern() {
+        let dir = create_files(&["a.json", "b.json.gz", "c.json"]);
+        let dir = dir.path();
+
+        let actual = expand(&[format!("{}/*.json", dir.display())]).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                Input::File(dir.join("a.json")),
+                Input::File(dir.join("c.json"))
+            ]
+        );
+        assert!(expand(&[format!("{}/*.avro", dir.display())]).is_err());
+    }
+}
@@ -1,51 +1,535 @@
-use clap::Parser;
-use schema2000::{render_schema, SchemaHypothesis};
-use std::error::Error;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
+use schema2000::{
+    diff_incoming, render_json_schema_with_options, Document, DocumentReader, InferredHypothesis,
+    SchemaHypothesis,
+};
+use serde_json::{json, Map, Value};
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, Write};
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
+use std::thread;
+use std::time::{Duration, Instant};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args = Args::parse();
+use crate::error::CliError;
+use crate::format::{BigQueryFallback, Draft, Format, SqlDialect};
+use crate::input::Input;
 
-    let reader: Box<dyn Read> = get_reader(args.file);
+mod error;
+mod format;
+mod input;
+mod output;
+mod signal;
 
-    let deserializer = serde_json::Deserializer::from_reader(reader);
-    let iterator = deserializer.into_iter::<serde_json::Value>();
+/// The flags that only apply to some formats, by their argument id, with these formats.
+const FORMAT_FLAGS: &[(&str, &[Format])] = &[
+    ("per_file", &[Format::JsonSchema]),
+    ("allow_empty_input", &[Format::JsonSchema]),
+    ("draft", &[Format::JsonSchema]),
+    ("id", &[Format::JsonSchema]),
+    ("description", &[Format::JsonSchema]),
+    ("closed", &[Format::JsonSchema]),
+    ("open", &[Format::JsonSchema]),
+    (
+        "title",
+        &[Format::JsonSchema, Format::OpenApi3_0, Format::OpenApi3_1],
+    ),
+    (
+        "pretty",
+        &[
+            Format::JsonSchema,
+            Format::OpenApi3_0,
+            Format::OpenApi3_1,
+            Format::Avro,
+            Format::BigQuery,
+        ],
+    ),
+    (
+        "compact",
+        &[
+            Format::JsonSchema,
+            Format::OpenApi3_0,
+            Format::OpenApi3_1,
+            Format::Avro,
+            Format::BigQuery,
+        ],
+    ),
+    (
+        "name",
+        &[
+            Format::OpenApi3_0,
+            Format::OpenApi3_1,
+            Format::TypeScript,
+            Format::Rust,
+            Format::Avro,
+            Format::Protobuf,
+            Format::Go,
+            Format::Pydantic,
+            Format::Kotlin,
+            Format::Java,
+            Format::Sql,
+        ],
+    ),
+    (
+        "namespace",
+        &[
+            Format::Avro,
+            Format::Protobuf,
+            Format::Go,
+            Format::Kotlin,
+            Format::Java,
+        ],
+    ),
+    ("map", &[Format::Avro]),
+    ("field_number", &[Format::Protobuf]),
+    ("dialect", &[Format::Sql]),
+    ("separator", &[Format::Sql]),
+    ("bigquery_fallback", &[Format::BigQuery]),
+];
+
+fn main() -> ExitCode {
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    for (id, formats) in FORMAT_FLAGS {
+        let given = matches.value_source(id) == Some(ValueSource::CommandLine);
+        if given && !formats.contains(&args.format) {
+            let names: Vec<String> = formats
+                .iter()
+                .filter_map(|format| format.to_possible_value())
+                .map(|value| value.get_name().to_string())
+                .collect();
+            let names = match names.split_last() {
+                Some((last, [])) => last.clone(),
+                Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+                None => String::new(),
+            };
+            Args::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!("--{} requires --format {names}", id.replace('_', "-")),
+                )
+                .exit();
+        }
+    }
+    if let Err(message) = format::check_field_numbers(&args.field_number) {
+        Args::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+
+    match run(&args) {
+        Ok(None) => ExitCode::SUCCESS,
+        // the schema of the documents read so far has been emitted, but the input is incomplete
+        Ok(Some(signal_exit_code)) => ExitCode::from(signal_exit_code),
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns the exit code of the signal that stopped reading the input, if any.
+fn run(args: &Args) -> Result<Option<u8>, CliError> {
+    let paths: Vec<String> = args.file.iter().chain(&args.paths).cloned().collect();
+    let inputs = input::expand(&paths)?;
+    if let Some(jobs) = args.jobs {
+        return run_parallel(args, &inputs, jobs).map(|()| None);
+    }
+
+    let mut invalid_records = args
+        .invalid_records
+        .as_ref()
+        .map(|path| {
+            File::create(path).map_err(|source| CliError::Open {
+                path: path.display().to_string(),
+                source,
+            })
+        })
+        .transpose()?;
+
+    let (sender, receiver) = mpsc::sync_channel(1024);
+    // signals only stop reading to emit the current schema if it is emitted periodically anyway;
+    // otherwise, they terminate the process as usual, so that a partial schema is never mistaken
+    // for a complete one
+    let stop = if args.emit_every.is_some() || args.emit_interval.is_some() {
+        // the stop is checked before every document, even if documents are queued; the event
+        // only wakes up the main thread if it is waiting for input, so it must not block if the
+        // queue is full
+        let signal_sender = sender.clone();
+        Some(signal::handle(move || {
+            let _ = signal_sender.try_send(Event::Terminate);
+        })?)
+    } else {
+        None
+    };
+    let reader_inputs = inputs.clone();
+    let skip_invalid = args.skip_invalid;
+    thread::spawn(move || read_documents(&reader_inputs, skip_invalid, &sender));
 
     let mut current_hypothesis: Option<SchemaHypothesis> = None;
+    let mut file_hypotheses: Vec<Option<SchemaHypothesis>> = vec![None; inputs.len()];
+    let mut file_document_counts: Vec<usize> = vec![0; inputs.len()];
+    let mut document_count: usize = 0;
+    let mut skipped_count: usize = 0;
+    // the number of documents the last emitted schema was derived from
+    let mut emitted_count: Option<usize> = None;
+    let interval = args
+        .emit_interval
+        .map(|seconds| Duration::from_secs(seconds.get()));
+    let mut next_emission = interval.map(|interval| Instant::now() + interval);
 
-    for json_document in iterator {
-        let new_hypo = schema2000::generate_hypothesis(&json_document?);
-        if current_hypothesis.is_none() {
-            current_hypothesis = Some(new_hypo);
-        } else {
-            current_hypothesis =
-                current_hypothesis.map(|cur| schema2000::merge_hypothesis(cur, new_hypo));
+    loop {
+        if stop.as_ref().and_then(signal::Stop::exit_code).is_some() {
+            break;
+        }
+        let event = match next_emission {
+            Some(deadline) => {
+                match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => {
+                        emit(current_hypothesis.as_ref(), &inputs, &file_hypotheses, args)?;
+                        if current_hypothesis.is_some() {
+                            emitted_count = Some(document_count);
+                        }
+                        next_emission = interval.map(|interval| deadline + interval);
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => Event::End,
+                }
+            }
+            None => receiver.recv().unwrap_or(Event::End),
+        };
+
+        let (
+            index,
+            Document {
+                value: InferredHypothesis(new_hypo),
+                offset,
+            },
+        ) = match event {
+            Event::Document(index, document) => (index, document),
+            Event::Invalid(index, error, record) => {
+                skipped_count += 1;
+                eprintln!("warning: skipping {}: {}", inputs[index], error);
+                if let (Some(file), Some(path)) = (&mut invalid_records, &args.invalid_records) {
+                    write_invalid_record(file, &record).map_err(|source| CliError::Write {
+                        target: path.display().to_string(),
+                        source,
+                    })?;
+                }
+                continue;
+            }
+            Event::Error(error) => return Err(error),
+            Event::End | Event::Terminate => break,
+        };
+        document_count += 1;
+        file_document_counts[index] += 1;
+
+        if args.per_file {
+            merge_into(&mut file_hypotheses[index], new_hypo.clone());
+        }
+        match &mut current_hypothesis {
+            Some(cur) if args.detect_drift && document_count > args.learning_window => {
+                for change in diff_incoming(cur, &new_hypo) {
+                    eprintln!(
+                        "{}: document {} (byte offset {}): {}",
+                        inputs[index], file_document_counts[index], offset, change
+                    );
+                }
+                cur.merge_in(new_hypo);
+            }
+            cur => merge_into(cur, new_hypo),
+        }
+
+        if matches!(args.emit_every, Some(n) if document_count.is_multiple_of(n.get())) {
+            emit(current_hypothesis.as_ref(), &inputs, &file_hypotheses, args)?;
+            emitted_count = Some(document_count);
         }
     }
 
-    let result = render_schema(&current_hypothesis.unwrap());
+    if skipped_count > 0 {
+        eprintln!("skipped {skipped_count} invalid document(s)");
+    }
+    if emitted_count != Some(document_count) {
+        // otherwise, the current schema has been emitted already
+        finish(current_hypothesis.as_ref(), &inputs, &file_hypotheses, args)?;
+    }
+    Ok(stop.as_ref().and_then(signal::Stop::exit_code))
+}
+
+/// Reads the inputs one after another, each one split across `jobs` worker threads.
+fn run_parallel(args: &Args, inputs: &[Input], jobs: NonZeroUsize) -> Result<(), CliError> {
+    let mut current_hypothesis: Option<SchemaHypothesis> = None;
+    let mut file_hypotheses: Vec<Option<SchemaHypothesis>> = Vec::with_capacity(inputs.len());
+
+    for input in inputs {
+        match schema2000::generate_hypothesis_parallel(input.open()?, jobs) {
+            Ok(new_hypo) => {
+                file_hypotheses.push(args.per_file.then(|| new_hypo.clone()));
+                merge_into(&mut current_hypothesis, new_hypo);
+            }
+            Err(schema2000::Error::EmptyInput) => file_hypotheses.push(None),
+            Err(source) => {
+                return Err(CliError::Input {
+                    input: Some(input.to_string()),
+                    source,
+                })
+            }
+        }
+    }
+
+    finish(current_hypothesis.as_ref(), inputs, &file_hypotheses, args)
+}
+
+fn finish(
+    hypothesis: Option<&SchemaHypothesis>,
+    inputs: &[Input],
+    file_hypotheses: &[Option<SchemaHypothesis>],
+    args: &Args,
+) -> Result<(), CliError> {
+    match hypothesis {
+        Some(_) => emit(hypothesis, inputs, file_hypotheses, args),
+        // the empty schema accepts every document
+        None if args.allow_empty_input => output::emit("{}", args.output.as_deref()),
+        None => Err(schema2000::Error::EmptyInput.into()),
+    }
+}
+
+fn merge_into(current: &mut Option<SchemaHypothesis>, new: SchemaHypothesis) {
+    match current {
+        Some(current) => current.merge_in(new),
+        None => *current = Some(new),
+    }
+}
+
+enum Event {
+    Document(usize, Document<InferredHypothesis>),
+    Invalid(usize, schema2000::Error, Vec<u8>),
+    Error(CliError),
+    End,
+    Terminate,
+}
+
+/// Derives the hypotheses of the JSON documents from all inputs and passes them on to the main
+/// thread, so that the main thread can emit the schema periodically or on signals while waiting
+/// for input.
+fn read_documents(inputs: &[Input], skip_invalid: bool, sender: &SyncSender<Event>) {
+    for (index, input) in inputs.iter().enumerate() {
+        let reader = match input.open() {
+            Ok(reader) => reader,
+            Err(error) => {
+                let _ = sender.send(Event::Error(error));
+                return;
+            }
+        };
+        let mut documents = if skip_invalid {
+            DocumentReader::ndjson(reader)
+        } else {
+            DocumentReader::new(reader)
+        };
 
-    println!("{result}");
+        while let Some(result) = documents.next() {
+            let event = match result {
+                Ok(document) => Event::Document(index, document),
+                Err(error @ schema2000::Error::InvalidJson { .. }) if skip_invalid => {
+                    Event::Invalid(index, error, documents.current_record().to_vec())
+                }
+                Err(source) => Event::Error(CliError::Input {
+                    input: Some(input.to_string()),
+                    source,
+                }),
+            };
+            let failed = matches!(event, Event::Error(_));
+            if sender.send(event).is_err() || failed {
+                return;
+            }
+        }
+    }
+    let _ = sender.send(Event::End);
+}
 
+fn write_invalid_record(file: &mut File, record: &[u8]) -> io::Result<()> {
+    file.write_all(record)?;
+    if !record.ends_with(b"\n") {
+        file.write_all(b"\n")?;
+    }
     Ok(())
 }
 
-fn get_reader(path: Option<String>) -> Box<dyn Read> {
-    if let Some(file_path) = path {
-        // Read from a file if the `--file` option is provided.
-        let file = File::open(file_path).expect("Failed to open file");
-        Box::new(file)
-    } else {
-        // Read from standard input if the `--file` option is not provided.
-        Box::new(io::stdin())
+fn emit(
+    hypothesis: Option<&SchemaHypothesis>,
+    inputs: &[Input],
+    file_hypotheses: &[Option<SchemaHypothesis>],
+    args: &Args,
+) -> Result<(), CliError> {
+    let hypothesis = match hypothesis {
+        Some(hypothesis) => hypothesis,
+        None => return Ok(()),
+    };
+    if !args.per_file {
+        return output::emit(&format::render(hypothesis, args), args.output.as_deref());
     }
+
+    let options = format::json_schema_options(args);
+    let files: Map<String, Value> = inputs
+        .iter()
+        .zip(file_hypotheses)
+        .filter_map(|(input, hypothesis)| {
+            let hypothesis = hypothesis.as_ref()?;
+            Some((
+                input.to_string(),
+                render_json_schema_with_options(hypothesis, &options),
+            ))
+        })
+        .collect();
+    let result = json!({
+        "combined": render_json_schema_with_options(hypothesis, &options),
+        "files": files,
+    });
+    output::emit(&format::to_json(&result, args), args.output.as_deref())
 }
 
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
+#[clap(name = "schema2000", author, version, about, long_about = None)]
 struct Args {
+    /// JSON files, directories (searched recursively) or glob patterns to read; reads stdin if none is given
+    paths: Vec<String>,
+
     #[clap(short, long)]
     /// JSON file path
     file: Option<String>,
+
+    #[clap(long)]
+    /// Emit the schema of every single file along with the combined schema
+    per_file: bool,
+
+    #[clap(
+        short,
+        long,
+        value_name = "N",
+        conflicts_with_all = ["skip_invalid", "emit_every", "emit_interval", "detect_drift"]
+    )]
+    /// Parse and merge every input on N threads in parallel; requires one document per line
+    jobs: Option<NonZeroUsize>,
+
+    #[clap(short, long)]
+    /// Write the schema to this file instead of stdout; the file is replaced atomically on every emission
+    output: Option<PathBuf>,
+
+    #[clap(long, value_enum, default_value_t = Format::JsonSchema)]
+    /// The format to render the schema in
+    format: Format,
+
+    #[clap(long, default_value = "Root")]
+    /// The name of the root type, for formats that declare named types
+    name: String,
+
+    #[clap(long, value_enum)]
+    /// Declare the schema as `$schema` of this JSON Schema draft and adhere to it
+    draft: Option<Draft>,
+
+    #[clap(long, value_name = "URI")]
+    /// The `$id` of the schema
+    id: Option<String>,
+
+    #[clap(long)]
+    /// The title of the schema
+    title: Option<String>,
+
+    #[clap(long)]
+    /// The description of the schema
+    description: Option<String>,
+
+    #[clap(long)]
+    /// Reject properties that have not been seen, by closing every object of the schema
+    closed: bool,
+
+    #[clap(long, value_name = "PATH", requires = "closed")]
+    /// Keep the object at PATH open, e.g. `$.metadata` or `$.items[*].labels`; can be repeated
+    open: Vec<String>,
+
+    #[clap(long)]
+    /// The namespace of the Avro records or the package of the declared types (its last segment for Go), e.g. `com.example`
+    namespace: Option<String>,
+
+    #[clap(long, value_name = "PATH")]
+    /// Render the object at PATH as an Avro map instead of a record, for objects with arbitrary keys; can be repeated
+    map: Vec<String>,
+
+    #[clap(long, value_name = "MESSAGE.PROPERTY=N", value_parser = format::parse_field_number)]
+    /// Number the field of PROPERTY in MESSAGE with N instead of alphabetically, e.g. `Order.orderId=7`; can be repeated
+    field_number: Vec<(String, u32)>,
+
+    #[clap(long, value_enum, default_value_t = SqlDialect::Postgres)]
+    /// The SQL dialect of the `CREATE TABLE` statements
+    dialect: SqlDialect,
+
+    #[clap(long, default_value = "_")]
+    /// The separator of the names of nested objects and their properties in SQL columns and tables
+    separator: String,
+
+    #[clap(long, value_enum, default_value_t = BigQueryFallback::Json)]
+    /// The BigQuery type of columns whose type BigQuery cannot express, e.g. of varying types
+    bigquery_fallback: BigQueryFallback,
+
+    #[clap(long, overrides_with = "compact")]
+    /// Pretty print the schema (default)
+    pretty: bool,
+
+    #[clap(long, overrides_with = "pretty")]
+    /// Print the schema on a single line
+    compact: bool,
+
+    #[clap(long)]
+    /// Skip invalid documents instead of aborting; requires one document per line
+    skip_invalid: bool,
+
+    #[clap(long, value_name = "FILE", requires = "skip_invalid")]
+    /// Write the skipped invalid documents to this file
+    invalid_records: Option<PathBuf>,
+
+    #[clap(long)]
+    /// Emit the empty schema `{}` instead of failing if the input does not contain any document
+    allow_empty_input: bool,
+
+    #[clap(long, value_name = "N")]
+    /// Emit the current schema after every N documents
+    emit_every: Option<NonZeroUsize>,
+
+    #[clap(long, value_name = "SECONDS")]
+    /// Emit the current schema every SECONDS seconds
+    emit_interval: Option<NonZeroU64>,
+
+    #[clap(long)]
+    /// Report every document that widens the schema to stderr, once the learning window has passed
+    detect_drift: bool,
+
+    #[clap(long, default_value_t = 100, requires = "detect_drift")]
+    /// Number of documents the schema is learned from before drift is reported
+    learning_window: usize,
+}
+
+#[cfg(test)]
+mod test {
+    use clap::CommandFactory;
+
+    use crate::{Args, FORMAT_FLAGS};
+
+    #[test]
+    fn test_format_flags_exist() {
+        let command = Args::command();
+        for (id, _) in FORMAT_FLAGS {
+            assert!(
+                command
+                    .get_arguments()
+                    .any(|argument| argument.get_id() == id),
+                "unknown flag {}",
+                id
+            );
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::error::CliError;
+
+/// Writes the rendered schema to `path`, or to stdout if no path is given.
+pub fn emit(content: &str, path: Option<&Path>) -> Result<(), CliError> {
+    match path {
+        Some(path) => write_atomically(path, content).map_err(|source| CliError::Write {
+            target: path.display().to_string(),
+            source,
+        }),
+        None => {
+            let mut stdout = io::stdout().lock();
+            write_content(&mut stdout, content)
+                .and_then(|()| stdout.flush())
+                .map_err(|source| CliError::Write {
+                    target: "stdout".to_string(),
+                    source,
+                })
+        }
+    }
+}
+
+/// Writes `content`, terminated by a line break.
+fn write_content<W: Write>(writer: &mut W, content: &str) -> io::Result<()> {
+    writer.write_all(content.as_bytes())?;
+    if !content.ends_with('\n') {
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Replaces the content of `path` without other processes ever seeing a partially written file:
+/// the content is written to a temporary file next to `path`, which is then renamed to `path`.
+fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file path", path.display()),
+        )
+    })?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut file = File::create(&temp_path)?;
+    write_content(&mut file, content)?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::output::write_atomically;
+
+    #[test]
+    fn test_write_atomically() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("schema.json");
+
+        write_atomically(&path, "{\"type\": \"integer\"}").unwrap();
+        write_atomically(&path, "{}").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}\n");
+        let files: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(files.len(), 1, "temporary file left behind");
+    }
+}
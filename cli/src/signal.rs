@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+use crate::error::CliError;
+
+/// The signal that stopped reading the input, as the exit code of a process terminated by it
+/// (`128 + signal`), or 0 if none has been received yet.
+#[derive(Clone)]
+pub struct Stop(Arc<AtomicU8>);
+
+impl Stop {
+    /// The exit code of the received signal, if any.
+    pub fn exit_code(&self) -> Option<u8> {
+        match self.0.load(Ordering::SeqCst) {
+            0 => None,
+            code => Some(code),
+        }
+    }
+
+    fn set(&self, exit_code: u8) {
+        self.0.store(exit_code, Ordering::SeqCst);
+    }
+}
+
+/// Calls `notify` once `SIGINT` (ctrl-c) or `SIGTERM` is received, instead of terminating.
+///
+/// Has to be called before any other thread is spawned: the signals are blocked in the calling
+/// thread and inherited by the threads it spawns, so that only the thread waiting for them
+/// receives them.
+#[cfg(unix)]
+pub fn handle<F: FnOnce() + Send + 'static>(notify: F) -> Result<Stop, CliError> {
+    use nix::sys::signal::{SigSet, Signal};
+
+    let stop = Stop(Arc::new(AtomicU8::new(0)));
+    let mut signals = SigSet::empty();
+    signals.add(Signal::SIGINT);
+    signals.add(Signal::SIGTERM);
+    signals
+        .thread_block()
+        .map_err(|errno| CliError::Signals(errno.into()))?;
+
+    let received = stop.clone();
+    std::thread::spawn(move || {
+        if let Ok(signal) = signals.wait() {
+            received.set(128 + signal as u8);
+            notify();
+        }
+    });
+    Ok(stop)
+}
+
+/// Calls `notify` once ctrl-c is received, instead of terminating.
+#[cfg(not(unix))]
+pub fn handle<F: FnOnce() + Send + 'static>(notify: F) -> Result<Stop, CliError> {
+    use std::sync::Mutex;
+
+    let stop = Stop(Arc::new(AtomicU8::new(0)));
+    let received = stop.clone();
+    let notify = Mutex::new(Some(notify));
+    ctrlc::set_handler(move || {
+        received.set(130);
+        if let Some(notify) = notify.lock().ok().and_then(|mut notify| notify.take()) {
+            notify();
+        }
+    })
+    .map_err(|error| CliError::Signals(std::io::Error::other(error)))?;
+    Ok(stop)
+}
@@ -0,0 +1,131 @@
+//! Runs the CLI for every case in `test-suite/cases`: a case is a directory containing the
+//! `input.jsonl` read from stdin, the output expected on stdout in `expected.schema.json` (or any
+//! other `expected.*` file, e.g. `expected.ts`) and optionally an `options` file with additional
+//! command line arguments, one per line.
+//!
+//! Run with `BLESS=1` to write the actual output to the expected file instead.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+fn cases_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-suite/cases")
+}
+
+fn read_options(case: &Path) -> Vec<String> {
+    match fs::read_to_string(case.join("options")) {
+        Ok(options) => options
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn run_case(case: &Path) -> Result<String, String> {
+    let input = fs::read(case.join("input.jsonl")).map_err(|e| format!("input.jsonl: {e}"))?;
+    let mut child = Command::new(env!("CARGO_BIN_EXE_schema2000"))
+        .args(read_options(case))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to start the CLI: {e}"))?;
+    child.stdin.take().unwrap().write_all(&input).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    if !output.status.success() {
+        return Err(format!(
+            "the CLI failed with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| format!("output is not UTF-8: {e}"))
+}
+
+/// The `expected.*` file of `case`, `expected.schema.json` if there is none yet.
+fn expected_path(case: &Path) -> PathBuf {
+    fs::read_dir(case)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("expected."))
+        })
+        .unwrap_or_else(|| case.join("expected.schema.json"))
+}
+
+/// Describes the first differing line, which is usually enough to spot the change.
+fn describe_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for number in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => {}
+            (None, None) => break,
+            (e, a) => {
+                return format!(
+                    "line {number}:\n- {}\n+ {}\n\nactual output:\n{actual}",
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>")
+                )
+            }
+        }
+    }
+    "outputs differ in line endings".to_string()
+}
+
+#[test]
+fn test_cases() {
+    let bless = std::env::var_os("BLESS").is_some_and(|bless| bless == "1");
+    let mut cases: Vec<PathBuf> = fs::read_dir(cases_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no cases found");
+
+    let mut failures = Vec::new();
+    for case in &cases {
+        let name = case.file_name().unwrap().to_string_lossy();
+        let actual = match run_case(case) {
+            Ok(actual) => actual,
+            Err(error) => {
+                failures.push(format!("{name}: {error}"));
+                continue;
+            }
+        };
+
+        let expected_path = expected_path(case);
+        if bless {
+            fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&expected_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{name}: {}",
+                describe_difference(&expected, &actual)
+            )),
+            Err(error) => failures.push(format!(
+                "{name}: {}: {error} (run with BLESS=1 to create it)",
+                expected_path.display()
+            )),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} case(s) failed:\n\n{}",
+        failures.len(),
+        cases.len(),
+        failures.join("\n\n")
+    );
+}
@@ -0,0 +1,59 @@
+//! Runs the CLI on input that does not end and stops it with a signal.
+#![cfg(unix)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, Command, Stdio};
+
+fn spawn(args: &[&str]) -> Child {
+    Command::new(env!("CARGO_BIN_EXE_schema2000"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap()
+}
+
+fn send(child: &Child, signal: &str) {
+    let status = Command::new("kill")
+        .args([signal, &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_emits_and_fails_on_signal_when_emitting_periodically() {
+    let mut child = spawn(&["--emit-every", "1", "--compact"]);
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"{\"a\": 1}\n").unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut schema = String::new();
+    stdout.read_line(&mut schema).unwrap();
+
+    send(&child, "-INT");
+    let status = child.wait().unwrap();
+
+    assert!(schema.contains("\"a\""), "unexpected schema {}", schema);
+    assert_eq!(status.code(), Some(130));
+    // the schema of all documents read has been emitted already
+    let mut rest = String::new();
+    stdout.read_line(&mut rest).unwrap();
+    assert_eq!(rest, "");
+    drop(stdin);
+}
+
+#[test]
+fn test_terminates_on_signal_otherwise() {
+    let mut child = spawn(&[]);
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"{\"a\": 1}\n").unwrap();
+
+    send(&child, "-TERM");
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(output.status.signal(), Some(15));
+    assert!(output.stdout.is_empty());
+    drop(stdin);
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Formatter};
+
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use crate::generate::{map_string_to_node, ArrayItems};
+use crate::model::{IntegerNode, NodeType, NumberNode, ObjectNode, ObjectProperty};
+use crate::SchemaHypothesis;
+
+/// The hypothesis of a JSON document, derived while deserializing the document.
+///
+/// Deserializing a JSON document into an `InferredHypothesis` derives the hypothesis of that
+/// document, just like [`generate_hypothesis`](crate::generate_hypothesis) does, but directly from
+/// the tokens of the document: the document is never held in memory as a whole, the memory needed
+/// is proportional to the size of the hypothesis only. This allows deriving the hypothesis of very
+/// large documents, e.g. with `serde_json::from_reader`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InferredHypothesis(pub SchemaHypothesis);
+
+impl<'de> Deserialize<'de> for InferredHypothesis {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let root = NodeTypeVisitor.deserialize(deserializer)?;
+        Ok(InferredHypothesis(SchemaHypothesis { root }))
+    }
+}
+
+impl From<InferredHypothesis> for SchemaHypothesis {
+    fn from(hypothesis: InferredHypothesis) -> Self {
+        hypothesis.0
+    }
+}
+
+struct NodeTypeVisitor;
+
+impl<'de> DeserializeSeed<'de> for NodeTypeVisitor {
+    type Value = NodeType;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<NodeType, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NodeTypeVisitor {
+    type Value = NodeType;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<NodeType, E> {
+        Ok(NodeType::Boolean)
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<NodeType, E> {
+        Ok(IntegerNode::new().into())
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<NodeType, E> {
+        Ok(IntegerNode::new().into())
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<NodeType, E> {
+        Ok(NumberNode::new().into())
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<NodeType, E> {
+        Ok(map_string_to_node(text))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<NodeType, E> {
+        Ok(NodeType::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<NodeType, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<NodeType, E> {
+        Ok(NodeType::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<NodeType, A::Error> {
+        let mut items = ArrayItems::default();
+        while let Some(item) = seq.next_element_seed(NodeTypeVisitor)? {
+            items.add(item);
+        }
+        Ok(items.into_array_node().into())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<NodeType, A::Error> {
+        let mut properties = BTreeMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let node_type = map.next_value_seed(NodeTypeVisitor)?;
+            properties.insert(key, ObjectProperty::new(node_type));
+        }
+        Ok(ObjectNode::new(properties).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::deserialize::InferredHypothesis;
+    use crate::generate_hypothesis;
+
+    #[test]
+    fn test_same_as_generate_hypothesis() {
+        let documents = vec![
+            json!(null),
+            json!(true),
+            json!(-1),
+            json!(18_446_744_073_709_551_615_u64),
+            json!(1.5),
+            json!("2000-01-01"),
+            json!("2000-01-01T00:00:00Z"),
+            json!("text"),
+            json!([]),
+            json!([1, "a", {"a": 1}, {"b": [null]}, [1], ["x"], "2000-01-01"]),
+            json!({"id": 1, "nested": {"list": [{"a": 1}, {"a": 1.5, "b": false}]}}),
+        ];
+
+        for document in documents {
+            let text = serde_json::to_string(&document).unwrap();
+            let InferredHypothesis(actual) = serde_json::from_str(&text).unwrap();
+            assert_eq!(actual, generate_hypothesis(&document), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let InferredHypothesis(actual) = serde_json::from_str(r#"{"a": 1, "a": "x"}"#).unwrap();
+
+        assert_eq!(actual, generate_hypothesis(&json!({"a": "x"})));
+    }
+
+    #[test]
+    fn test_large_array() {
+        let mut text = String::from("[");
+        for i in 0..100_000 {
+            text.push_str(&format!("{{\"id\": {}, \"name\": \"n{}\"}},", i, i));
+        }
+        text.push_str("{\"id\": 0}]");
+
+        let InferredHypothesis(actual) = serde_json::from_str(&text).unwrap();
+
+        let expected = generate_hypothesis(&json!([{"id": 1, "name": "a"}, {"id": 0}]));
+        assert_eq!(actual, expected);
+    }
+}
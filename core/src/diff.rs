@@ -0,0 +1,390 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+use crate::merge::merge_node_type;
+use crate::model::{ArrayNode, NodeType, ObjectNode, SchemaHypothesis};
+
+/// A single way in which a hypothesis has been widened compared to a previous one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaChange {
+    /// A property that has never been seen before.
+    PropertyAdded { path: String, node_type: NodeType },
+    /// A property that has been present in every document so far is missing.
+    RequiredPropertyMissing { path: String },
+    /// A new alternative has been added to the types seen at `path`.
+    TypeAdded { path: String, node_type: NodeType },
+    /// The type at `path` has been replaced by a wider one.
+    TypeChanged {
+        path: String,
+        from: NodeType,
+        to: NodeType,
+    },
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::PropertyAdded { path, node_type } => {
+                write!(f, "property added: {path} ({})", type_name(node_type))
+            }
+            SchemaChange::RequiredPropertyMissing { path } => {
+                write!(f, "required property missing: {path}")
+            }
+            SchemaChange::TypeAdded { path, node_type } => {
+                write!(f, "type added: {path} ({})", type_name(node_type))
+            }
+            SchemaChange::TypeChanged { path, from, to } => write!(
+                f,
+                "type changed: {path} ({} -> {})",
+                type_name(from),
+                type_name(to)
+            ),
+        }
+    }
+}
+
+/// Lists the changes that turn `old` into `new`, where `new` is expected to be the result of
+/// merging further documents into `old`.
+#[must_use]
+pub fn diff_hypothesis(old: &SchemaHypothesis, new: &SchemaHypothesis) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    diff_node_type("$", &old.root, &new.root, &mut changes);
+    changes
+}
+
+/// Lists the changes merging `incoming` into `old` would make, without merging it. Unlike
+/// [`diff_hypothesis`], this does not need a copy of `old`; only the parts that change are copied.
+#[must_use]
+pub fn diff_incoming(old: &SchemaHypothesis, incoming: &SchemaHypothesis) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    diff_incoming_node_type("$", &old.root, &incoming.root, &mut changes);
+    changes
+}
+
+fn diff_incoming_node_type(
+    path: &str,
+    old: &NodeType,
+    incoming: &NodeType,
+    changes: &mut Vec<SchemaChange>,
+) {
+    match (old, incoming) {
+        (old, incoming) if old == incoming => {}
+        (NodeType::Object(old), NodeType::Object(incoming)) => {
+            let keys: BTreeSet<&String> = old
+                .properties
+                .keys()
+                .chain(incoming.properties.keys())
+                .collect();
+            for key in keys {
+                let property_path = format!("{path}.{key}");
+                match (old.properties.get(key), incoming.properties.get(key)) {
+                    (None, Some(incoming_property)) => changes.push(SchemaChange::PropertyAdded {
+                        path: property_path,
+                        node_type: incoming_property.node_type.clone(),
+                    }),
+                    (Some(old_property), None) if old_property.required => {
+                        changes.push(SchemaChange::RequiredPropertyMissing {
+                            path: property_path,
+                        });
+                    }
+                    (Some(old_property), Some(incoming_property)) => {
+                        if old_property.required && !incoming_property.required {
+                            changes.push(SchemaChange::RequiredPropertyMissing {
+                                path: property_path.clone(),
+                            });
+                        }
+                        diff_incoming_node_type(
+                            &property_path,
+                            &old_property.node_type,
+                            &incoming_property.node_type,
+                            changes,
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (NodeType::Array(old), NodeType::Array(incoming)) => match (&old.items, &incoming.items) {
+            (Some(old), Some(incoming)) => {
+                diff_incoming_node_type(&format!("{path}[*]"), old, incoming, changes);
+            }
+            (None, Some(incoming)) => changes.push(SchemaChange::TypeAdded {
+                path: format!("{path}[*]"),
+                node_type: incoming.as_ref().clone(),
+            }),
+            (_, None) => {}
+        },
+        (old, incoming) => {
+            let old_nodes: Vec<&NodeType> = match old {
+                NodeType::Any(any) => any.nodes.iter().collect(),
+                old => vec![old],
+            };
+            let incoming_nodes: Vec<&NodeType> = match incoming {
+                NodeType::Any(any) => any.nodes.iter().collect(),
+                incoming => vec![incoming],
+            };
+            // objects and arrays are merged with their counterpart of the same kind, the other
+            // alternatives are added unless they are known already
+            for node_type in incoming_nodes {
+                let counterpart = old_nodes.iter().find(|other| {
+                    (node_type.is_object() && other.is_object())
+                        || (node_type.is_array() && other.is_array())
+                });
+                let known = old_nodes.contains(&node_type)
+                    || (matches!(node_type, NodeType::Date(_) | NodeType::DateTime(_))
+                        && old_nodes
+                            .iter()
+                            .any(|other| matches!(other, NodeType::String(_))));
+                match counterpart {
+                    Some(other) => diff_incoming_node_type(path, other, node_type, changes),
+                    None if known => {}
+                    None => {
+                        let merged = merge_node_type(old.clone(), node_type.clone());
+                        diff_node_type(path, old, &merged, changes);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn diff_node_type(path: &str, old: &NodeType, new: &NodeType, changes: &mut Vec<SchemaChange>) {
+    match (old, new) {
+        (old, new) if old == new => {}
+        (NodeType::Object(old), NodeType::Object(new)) => diff_object(path, old, new, changes),
+        (NodeType::Array(old), NodeType::Array(new)) => diff_array(path, old, new, changes),
+        (NodeType::Any(old), NodeType::Any(new)) => diff_any(path, &old.nodes, &new.nodes, changes),
+        (old, NodeType::Any(new)) if new.nodes.contains(old) || !is_scalar(old) => {
+            let old = std::iter::once(old.clone()).collect();
+            diff_any(path, &old, &new.nodes, changes);
+        }
+        (old, new) => changes.push(SchemaChange::TypeChanged {
+            path: path.to_string(),
+            from: old.clone(),
+            to: new.clone(),
+        }),
+    }
+}
+
+fn diff_object(path: &str, old: &ObjectNode, new: &ObjectNode, changes: &mut Vec<SchemaChange>) {
+    for (key, new_property) in &new.properties {
+        let property_path = format!("{path}.{key}");
+        match old.properties.get(key) {
+            None => changes.push(SchemaChange::PropertyAdded {
+                path: property_path,
+                node_type: new_property.node_type.clone(),
+            }),
+            Some(old_property) => {
+                if old_property.required && !new_property.required {
+                    changes.push(SchemaChange::RequiredPropertyMissing {
+                        path: property_path.clone(),
+                    });
+                }
+                diff_node_type(
+                    &property_path,
+                    &old_property.node_type,
+                    &new_property.node_type,
+                    changes,
+                );
+            }
+        }
+    }
+}
+
+fn diff_array(path: &str, old: &ArrayNode, new: &ArrayNode, changes: &mut Vec<SchemaChange>) {
+    let items_path = format!("{path}[*]");
+    match (&old.items, &new.items) {
+        (Some(old), Some(new)) => diff_node_type(&items_path, old, new, changes),
+        (None, Some(new)) => changes.push(SchemaChange::TypeAdded {
+            path: items_path,
+            node_type: new.as_ref().clone(),
+        }),
+        (_, None) => {}
+    }
+}
+
+fn diff_any(
+    path: &str,
+    old: &BTreeSet<NodeType>,
+    new: &BTreeSet<NodeType>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for node_type in new.difference(old) {
+        let counterpart = old.iter().find(|other| {
+            (node_type.is_object() && other.is_object())
+                || (node_type.is_array() && other.is_array())
+        });
+        match counterpart {
+            Some(other) => diff_node_type(path, other, node_type, changes),
+            None => changes.push(SchemaChange::TypeAdded {
+                path: path.to_string(),
+                node_type: node_type.clone(),
+            }),
+        }
+    }
+}
+
+fn is_scalar(node_type: &NodeType) -> bool {
+    !node_type.is_object() && !node_type.is_array()
+}
+
+fn type_name(node_type: &NodeType) -> String {
+    match node_type {
+        NodeType::Any(any) => any
+            .nodes
+            .iter()
+            .map(type_name)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        NodeType::Array(_) => "array".to_string(),
+        NodeType::Boolean => "boolean".to_string(),
+        NodeType::Integer(_) => "integer".to_string(),
+        NodeType::Null => "null".to_string(),
+        NodeType::Number(_) => "number".to_string(),
+        NodeType::Object(_) => "object".to_string(),
+        NodeType::String(_) => "string".to_string(),
+        NodeType::DateTime(_) => "date-time".to_string(),
+        NodeType::Date(_) => "date".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::{btreemap, btreeset};
+    use serde_json::json;
+
+    use crate::diff::{diff_hypothesis, diff_incoming, SchemaChange};
+    use crate::model::{
+        AnyNode, ArrayNode, DateNode, IntegerNode, NodeType, ObjectNode, ObjectProperty,
+        SchemaHypothesis, StringNode,
+    };
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    fn widen(old: &SchemaHypothesis, document: &serde_json::Value) -> Vec<SchemaChange> {
+        let incoming = generate_hypothesis(document);
+        let changes = diff_incoming(old, &incoming);
+        let new = merge_hypothesis(old.clone(), incoming);
+        assert_eq!(changes, diff_hypothesis(old, &new));
+        changes
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let old = generate_hypothesis(&json!({"id": 1, "tags": ["a"]}));
+
+        assert_eq!(widen(&old, &json!({"id": 2, "tags": []})), vec![]);
+    }
+
+    #[test]
+    fn test_property_added() {
+        let old = generate_hypothesis(&json!({"id": 1}));
+
+        assert_eq!(
+            widen(&old, &json!({"id": 2, "name": "x"})),
+            vec![SchemaChange::PropertyAdded {
+                path: "$.name".to_string(),
+                node_type: StringNode::new().into()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_required_property_missing() {
+        let old = generate_hypothesis(&json!({"id": 1, "name": "x"}));
+
+        assert_eq!(
+            widen(&old, &json!({"id": 2})),
+            vec![SchemaChange::RequiredPropertyMissing {
+                path: "$.name".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_type_added() {
+        let old = generate_hypothesis(&json!({"id": 1}));
+
+        assert_eq!(
+            widen(&old, &json!({"id": "1"})),
+            vec![SchemaChange::TypeAdded {
+                path: "$.id".to_string(),
+                node_type: StringNode::new().into()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_type_changed() {
+        let old = generate_hypothesis(&json!({"day": "2000-01-01"}));
+
+        assert_eq!(
+            widen(&old, &json!({"day": "tomorrow"})),
+            vec![SchemaChange::TypeChanged {
+                path: "$.day".to_string(),
+                from: DateNode::new().into(),
+                to: StringNode::new().into()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_nested_in_array_and_any() {
+        let old = SchemaHypothesis::new(ArrayNode::new(
+            AnyNode::new(btreeset![
+                IntegerNode::new().into(),
+                ObjectNode::new(btreemap! {
+                    "id".to_string() => ObjectProperty::new(IntegerNode::new())
+                })
+                .into()
+            ])
+            .into(),
+        ));
+
+        assert_eq!(
+            widen(&old, &json!([{"id": 1, "extra": null}])),
+            vec![SchemaChange::PropertyAdded {
+                path: "$[*].extra".to_string(),
+                node_type: NodeType::Null
+            }]
+        );
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let old = merge_hypothesis(
+            generate_hypothesis(&json!({"a": 1, "b": [1, {"id": 1}]})),
+            generate_hypothesis(&json!({"a": "x", "b": ["2000-01-01"]})),
+        );
+
+        assert_eq!(
+            widen(
+                &old,
+                &json!({"a": "2000-01-01", "b": [{"id": 2, "x": true}, false]})
+            ),
+            vec![
+                SchemaChange::TypeAdded {
+                    path: "$.b[*]".to_string(),
+                    node_type: NodeType::Boolean
+                },
+                SchemaChange::PropertyAdded {
+                    path: "$.b[*].x".to_string(),
+                    node_type: NodeType::Boolean
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let change = SchemaChange::TypeChanged {
+            path: "$.a".to_string(),
+            from: IntegerNode::new().into(),
+            to: AnyNode::new(btreeset![IntegerNode::new().into(), NodeType::Null]).into(),
+        };
+
+        assert_eq!(
+            change.to_string(),
+            "type changed: $.a (integer -> integer | null)"
+        );
+    }
+}
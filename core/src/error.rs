@@ -0,0 +1,73 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// The input contains a document that is not valid JSON. `offset` is the byte offset at which
+    /// the invalid document starts, `line` and `column` point to the error within the input.
+    InvalidJson {
+        line: usize,
+        column: usize,
+        offset: usize,
+        source: serde_json::Error,
+    },
+    /// The input does not contain a single JSON document to derive a schema from.
+    EmptyInput,
+}
+
+impl Error {
+    pub(crate) fn from_json(source: serde_json::Error, line: usize, offset: usize) -> Self {
+        if source.is_io() {
+            return Error::Io(source.into());
+        }
+        Error::InvalidJson {
+            line,
+            column: source.column(),
+            offset,
+            source,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(source) => write!(f, "failed to read input: {source}"),
+            Error::InvalidJson {
+                line,
+                column,
+                offset,
+                source,
+            } => {
+                // serde_json appends the position relative to what it has parsed, which is not
+                // necessarily the position within the whole input
+                let message = source.to_string();
+                let suffix = format!(" at line {} column {}", source.line(), source.column());
+                let message = message.strip_suffix(&suffix).unwrap_or(&message);
+                write!(
+                    f,
+                    "invalid JSON at line {line}, column {column} (document starting at byte offset {offset}): {message}"
+                )
+            }
+            Error::EmptyInput => write!(f, "the input does not contain any JSON document"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(source) => Some(source),
+            Error::InvalidJson { source, .. } => Some(source),
+            Error::EmptyInput => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io(source)
+    }
+}
@@ -1,12 +1,16 @@
 use chrono::{DateTime, NaiveDate};
 use serde_json::{Map, Number, Value};
 use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
 
+use crate::deserialize::InferredHypothesis;
 use crate::model::{
     AnyNode, ArrayNode, DateNode, DateTimeNode, IntegerNode, NodeType, NumberNode, ObjectNode,
     ObjectProperty, SchemaHypothesis, StringNode,
 };
+use crate::reader::DocumentReader;
 use crate::utils::SetVariances;
+use crate::Error;
 
 fn generate_properties(properties: &Map<String, Value>) -> BTreeMap<String, ObjectProperty> {
     properties
@@ -30,11 +34,11 @@ fn generate_node_type(dom: &Value) -> NodeType {
         Value::Number(i) => map_number_to_node(i),
         Value::String(s) => map_string_to_node(s),
         Value::Array(array_values) => {
-            if array_values.is_empty() {
-                ArrayNode::new_untyped().into()
-            } else {
-                ArrayNode::new(generate_node_type_for_array_values(array_values)).into()
+            let mut items = ArrayItems::default();
+            for value in array_values {
+                items.add(generate_node_type(value));
             }
+            items.into_array_node().into()
         }
         Value::Object(props) => ObjectNode::new(generate_properties(props)).into(),
     }
@@ -47,7 +51,7 @@ fn map_number_to_node(nr: &Number) -> NodeType {
     IntegerNode::new().into()
 }
 
-fn map_string_to_node(text: &str) -> NodeType {
+pub(crate) fn map_string_to_node(text: &str) -> NodeType {
     if DateTime::parse_from_rfc3339(text).is_ok() {
         return DateTimeNode::new().into();
     } else if NaiveDate::parse_from_str(text, "%F").is_ok() {
@@ -56,43 +60,47 @@ fn map_string_to_node(text: &str) -> NodeType {
     StringNode::new().into()
 }
 
-fn generate_node_type_for_array_values(array_values: &[Value]) -> NodeType {
-    let mut merged_obj_type: Option<NodeType> = None;
-    let mut merged_array_type: Option<NodeType> = None;
-    let mut types = BTreeSet::new();
+/// Collects the types of the items of an array: all objects are merged into a single object type
+/// and all arrays into a single array type, other types are kept side by side.
+#[derive(Default)]
+pub(crate) struct ArrayItems {
+    merged_obj_type: Option<NodeType>,
+    merged_array_type: Option<NodeType>,
+    types: BTreeSet<NodeType>,
+}
 
-    for value in array_values {
-        let value_type = generate_node_type(value);
+impl ArrayItems {
+    pub(crate) fn add(&mut self, value_type: NodeType) {
         match value_type {
-            NodeType::Object(ObjectNode { properties: _ }) => {
-                merged_obj_type = match merged_obj_type {
-                    Some(acc) => Some(crate::merge::merge_node_type(acc, value_type)),
-                    None => Some(value_type),
-                };
-            }
-            NodeType::Array(_) => {
-                merged_array_type = match merged_array_type {
-                    Some(acc) => Some(crate::merge::merge_node_type(acc, value_type)),
-                    None => Some(value_type),
-                }
-            }
+            NodeType::Object(ObjectNode { properties: _ }) => match &mut self.merged_obj_type {
+                Some(acc) => crate::merge::merge_node_type_in_place(acc, value_type),
+                None => self.merged_obj_type = Some(value_type),
+            },
+            NodeType::Array(_) => match &mut self.merged_array_type {
+                Some(acc) => crate::merge::merge_node_type_in_place(acc, value_type),
+                None => self.merged_array_type = Some(value_type),
+            },
             _ => {
-                types.insert(value_type);
+                self.types.insert(value_type);
             }
         };
     }
-    if let Some(node_type) = merged_obj_type {
-        types.insert(node_type);
-    }
 
-    if let Some(node_type) = merged_array_type {
-        types.insert(node_type);
-    }
+    pub(crate) fn into_array_node(self) -> ArrayNode {
+        let mut types = self.types;
+        if let Some(node_type) = self.merged_obj_type {
+            types.insert(node_type);
+        }
 
-    match SetVariances::new(&types) {
-        SetVariances::Empty => unreachable!(),
-        SetVariances::OneElement(node_type) => node_type.clone(),
-        SetVariances::Multiple(_) => AnyNode::new(types).into(),
+        if let Some(node_type) = self.merged_array_type {
+            types.insert(node_type);
+        }
+
+        match SetVariances::new(&types) {
+            SetVariances::Empty => ArrayNode::new_untyped(),
+            SetVariances::OneElement(node_type) => ArrayNode::new(node_type.clone()),
+            SetVariances::Multiple(_) => ArrayNode::new(AnyNode::new(types).into()),
+        }
     }
 }
 
@@ -103,17 +111,35 @@ pub fn generate_hypothesis(dom: &Value) -> SchemaHypothesis {
     }
 }
 
+/// Derives a hypothesis from all JSON documents read from `reader`.
+///
+/// # Errors
+///
+/// Fails if reading the input fails, the input contains invalid JSON or no document at all.
+pub fn generate_hypothesis_from_reader<R: Read>(reader: R) -> Result<SchemaHypothesis, Error> {
+    let mut current_hypothesis: Option<SchemaHypothesis> = None;
+    for document in DocumentReader::<R, InferredHypothesis>::new(reader) {
+        let new_hypo = document?.value.0;
+        match &mut current_hypothesis {
+            None => current_hypothesis = Some(new_hypo),
+            Some(cur) => cur.merge_in(new_hypo),
+        }
+    }
+    current_hypothesis.ok_or(Error::EmptyInput)
+}
+
 #[cfg(test)]
 mod test {
     use maplit::{btreemap, btreeset};
     use parameterized::{ide, parameterized};
     use serde_json::json;
 
-    use crate::generate::generate_node_type;
+    use crate::generate::{generate_hypothesis_from_reader, generate_node_type};
     use crate::model::{
         AnyNode, ArrayNode, DateNode, DateTimeNode, IntegerNode, NodeType, NumberNode, ObjectNode,
         ObjectProperty, StringNode,
     };
+    use crate::Error;
 
     #[test]
     fn test_null() {
@@ -237,4 +263,25 @@ mod test {
 
         assert_eq!(generate_node_type(&dom), expected);
     }
+
+    #[test]
+    fn test_from_reader() {
+        let input = "{\"id\": 1}\n{\"id\": 2, \"name\": \"x\"}\n";
+
+        let actual = generate_hypothesis_from_reader(input.as_bytes()).unwrap();
+
+        let expected = ObjectNode::new(btreemap! {
+            "id".to_string() => ObjectProperty::new(IntegerNode::new()),
+            "name".to_string() => ObjectProperty::new(StringNode::new()).optional(),
+        })
+        .into();
+        assert_eq!(actual.root, expected);
+    }
+
+    #[test]
+    fn test_from_reader_empty_input() {
+        let actual = generate_hypothesis_from_reader(" \n".as_bytes());
+
+        assert!(matches!(actual, Err(Error::EmptyInput)));
+    }
 }
@@ -1,12 +1,28 @@
 #![allow(clippy::module_name_repetitions)]
 
-pub use generate::generate_hypothesis;
-pub use merge::merge_hypothesis;
+pub use deserialize::InferredHypothesis;
+pub use diff::{diff_hypothesis, diff_incoming, SchemaChange};
+pub use error::Error;
+pub use generate::{generate_hypothesis, generate_hypothesis_from_reader};
+pub use merge::{merge_hypothesis, merge_node_type};
 pub use model::SchemaHypothesis;
-pub use renderer::render_schema;
+pub use parallel::generate_hypothesis_parallel;
+pub use reader::{Document, DocumentReader};
+pub use renderer::{
+    render_avro, render_bigquery, render_go, render_java, render_json_schema,
+    render_json_schema_with_options, render_kotlin, render_openapi, render_protobuf,
+    render_pydantic, render_rust, render_schema, render_sql, render_typescript, AvroOptions,
+    AvroSchema, BigQueryFallback, BigQueryOptions, BigQuerySchema, Draft, JsonSchemaOptions,
+    OpenApiOptions, OpenApiVersion, ProtobufOptions, SqlDialect, SqlOptions,
+};
 
+mod deserialize;
+mod diff;
+mod error;
 mod generate;
 mod merge;
 pub mod model;
+mod parallel;
+mod reader;
 mod renderer;
 mod utils;
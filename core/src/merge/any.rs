@@ -1,38 +1,45 @@
+use std::collections::BTreeSet;
+
+use maplit::btreeset;
+
 use crate::merge;
 use crate::model::{AnyNode, NodeType};
+use crate::utils::SetVariances;
 
-pub fn merge_any(xs: &AnyNode, ys: AnyNode) -> NodeType {
-    let xs = &xs.nodes;
-    let ys = ys.nodes;
+/// Adds `other` (or all of its alternatives, if it is an `AnyNode` itself) to the alternatives of
+/// `xs`. Objects are merged with the object alternative and arrays with the array alternative.
+pub fn merge_any(xs: AnyNode, other: NodeType) -> NodeType {
+    let ys = match other {
+        NodeType::Any(ys) => ys.nodes,
+        other => btreeset![other],
+    };
 
-    let mut zs = xs.clone();
+    let mut zs: Vec<NodeType> = xs.nodes.into_iter().collect();
     for node_type in ys {
-        match node_type {
-            node @ NodeType::Object { .. } => match xs.iter().find(|x| x.is_object()) {
-                None => {
-                    zs.insert(node);
-                }
-                Some(other @ NodeType::Object { .. }) => {
-                    zs.remove(other);
-                    zs.insert(merge::merge_node_type(node, other.clone()));
-                }
-                Some(_) => unreachable!(),
-            },
-            node @ NodeType::Array(_) => match xs.iter().find(|x| x.is_array()) {
-                None => {
-                    zs.insert(node);
-                }
-                Some(other @ NodeType::Array(_)) => {
-                    zs.remove(other);
-                    zs.insert(merge::merge_node_type(node, other.clone()));
+        let same_kind = zs.iter_mut().find(|x| {
+            (x.is_object() && node_type.is_object()) || (x.is_array() && node_type.is_array())
+        });
+        match same_kind {
+            Some(other) => merge::merge_node_type_in_place(other, node_type),
+            None => {
+                if !zs.contains(&node_type) {
+                    zs.push(node_type);
                 }
-                Some(_) => unreachable!(),
-            },
-            _ => {
-                zs.insert(node_type);
             }
         }
     }
 
-    AnyNode::new(zs).into()
+    normalize(zs.into_iter().collect())
+}
+
+/// Strings subsume dates and date-times (just like merging them directly does), which keeps
+/// merging associative. A single remaining alternative is not wrapped in an `AnyNode`.
+fn normalize(mut nodes: BTreeSet<NodeType>) -> NodeType {
+    if nodes.iter().any(|node| matches!(node, NodeType::String(_))) {
+        nodes.retain(|node| !matches!(node, NodeType::Date(_) | NodeType::DateTime(_)));
+    }
+    match SetVariances::new(&nodes) {
+        SetVariances::OneElement(node) => node.clone(),
+        SetVariances::Empty | SetVariances::Multiple(_) => AnyNode::new(nodes).into(),
+    }
 }
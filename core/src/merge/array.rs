@@ -1,12 +1,10 @@
-use crate::merge::merge_node_type;
+use crate::merge::merge_node_type_in_place;
 use crate::model::ArrayNode;
 
-pub fn merge_array(a: ArrayNode, b: ArrayNode) -> ArrayNode {
-    match (a, b) {
-        (ArrayNode { items: None }, ys) => ys,
-        (xs, ArrayNode { items: None }) => xs,
-        (ArrayNode { items: Some(xs) }, ArrayNode { items: Some(ys) }) => {
-            ArrayNode::new(merge_node_type(*xs, *ys))
-        }
+pub fn merge_array(a: &mut ArrayNode, b: ArrayNode) {
+    match (&mut a.items, b.items) {
+        (_, None) => {}
+        (items @ None, ys) => *items = ys,
+        (Some(xs), Some(ys)) => merge_node_type_in_place(xs, *ys),
     }
 }
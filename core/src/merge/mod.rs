@@ -1,6 +1,8 @@
+use std::mem;
+
 use crate::merge::array::merge_array;
 use crate::merge::object::merge_object;
-use crate::model::{AnyNode, DateNode, DateTimeNode, NodeType, SchemaHypothesis, StringNode};
+use crate::model::{AnyNode, NodeType, SchemaHypothesis, StringNode};
 use maplit::btreeset;
 
 mod any;
@@ -10,41 +12,46 @@ mod object_property;
 
 #[must_use]
 pub fn merge_hypothesis(a: SchemaHypothesis, b: SchemaHypothesis) -> SchemaHypothesis {
-    let root = merge_node_type(a.root, b.root);
-    SchemaHypothesis { root }
+    let mut a = a;
+    a.merge_in(b);
+    a
 }
 
+#[must_use]
 pub fn merge_node_type(a: NodeType, b: NodeType) -> NodeType {
+    let mut a = a;
+    merge_node_type_in_place(&mut a, b);
+    a
+}
+
+/// Merges `other` into `target`, reusing the nodes of `target` instead of copying them.
+pub fn merge_node_type_in_place(target: &mut NodeType, other: NodeType) {
+    match (&mut *target, other) {
+        (NodeType::Object(a), NodeType::Object(b)) => merge_object(a, b),
+        (NodeType::Array(a), NodeType::Array(b)) => merge_array(a, b),
+        (_, other) => {
+            let node_type = mem::replace(target, NodeType::Null);
+            *target = merge_different_kinds(node_type, other);
+        }
+    }
+}
+
+fn merge_different_kinds(a: NodeType, b: NodeType) -> NodeType {
     match (a, b) {
+        (NodeType::Any(xs), other) | (other, NodeType::Any(xs)) => any::merge_any(xs, other),
         (a, b) if a == b => a,
-        (NodeType::Object(a), NodeType::Object(b)) => merge_object(a, b).into(),
-        (NodeType::Date(_), NodeType::String(_)) | (NodeType::String(_), NodeType::Date(_)) => {
+        (NodeType::Date(_) | NodeType::DateTime(_), NodeType::String(_))
+        | (NodeType::String(_), NodeType::Date(_) | NodeType::DateTime(_)) => {
             StringNode::new().into()
         }
-        (NodeType::DateTime(_), NodeType::String(_))
-        | (NodeType::String(_), NodeType::DateTime(_)) => StringNode::new().into(),
-        (NodeType::Date(_), NodeType::DateTime(_)) | (NodeType::DateTime(_), NodeType::Date(_)) => {
-            AnyNode::new(btreeset![
-                DateNode::new().into(),
-                DateTimeNode::new().into()
-            ])
-            .into()
-        }
-        (NodeType::Array(a), NodeType::Array(b)) => merge_array(a, b).into(),
-        (NodeType::Any(xs), NodeType::Any(ys)) => any::merge_any(&xs, ys),
-        (a @ NodeType::Any(_), b) | (b, a @ NodeType::Any(_)) => {
-            merge_node_type(a, AnyNode::new(btreeset![b]).into())
-        }
-        (a, b) => merge_node_type(
-            AnyNode::new(btreeset![a]).into(),
-            AnyNode::new(btreeset![b]).into(),
-        ),
+        (a, b) => AnyNode::new(btreeset![a, b]).into(),
     }
 }
 
 #[cfg(test)]
 mod test {
     use maplit::{btreemap, btreeset};
+    use serde_json::json;
 
     use crate::merge::{merge_hypothesis, merge_node_type};
     use crate::model::{
@@ -62,6 +69,42 @@ mod test {
         assert_eq!(actual, SchemaHypothesis::new(StringNode::new()));
     }
 
+    #[test]
+    fn test_merge_in() {
+        let mut a = SchemaHypothesis::new(ObjectNode::new(btreemap! {
+            String::from("id") => ObjectProperty::new(IntegerNode::new()),
+            String::from("name") => ObjectProperty::new(StringNode::new())
+        }));
+        let b = SchemaHypothesis::new(ObjectNode::new(btreemap! {
+            String::from("id") => ObjectProperty::new(StringNode::new()),
+            String::from("tags") => ObjectProperty::new(ArrayNode::new_untyped())
+        }));
+        let expected = merge_hypothesis(a.clone(), b.clone());
+
+        a.merge_in(b);
+
+        assert_eq!(a, expected);
+    }
+
+    #[test]
+    fn test_observe() {
+        let mut hypothesis = SchemaHypothesis::new(ObjectNode::new(btreemap! {
+            String::from("id") => ObjectProperty::new(IntegerNode::new())
+        }));
+
+        hypothesis.observe(&json!({"id": null}));
+
+        assert_eq!(
+            hypothesis,
+            SchemaHypothesis::new(ObjectNode::new(btreemap! {
+                String::from("id") => ObjectProperty::new(AnyNode::new(btreeset![
+                    IntegerNode::new().into(),
+                    NodeType::Null
+                ]))
+            }))
+        );
+    }
+
     #[test]
     fn test_merge_array_without_types() {
         let a = ArrayNode::new_untyped();
@@ -280,6 +323,35 @@ mod test {
         assert_eq!(actual_swapped, StringNode::new().into());
     }
 
+    #[test]
+    fn test_merge_string_into_dates() {
+        let dates = merge_node_type(DateNode::new().into(), DateTimeNode::new().into());
+
+        assert_eq!(
+            merge_node_type(dates, StringNode::new().into()),
+            StringNode::new().into()
+        );
+    }
+
+    #[test]
+    fn test_merge_string_into_dates_and_other_types() {
+        let a = AnyNode::new(btreeset![
+            DateNode::new().into(),
+            DateTimeNode::new().into(),
+            IntegerNode::new().into()
+        ])
+        .into();
+
+        assert_eq!(
+            merge_node_type(a, StringNode::new().into()),
+            AnyNode::new(btreeset![
+                IntegerNode::new().into(),
+                StringNode::new().into()
+            ])
+            .into()
+        );
+    }
+
     #[test]
     fn test_merge_date_and_datetime() {
         let actual = merge_node_type(DateTimeNode::new().into(), DateNode::new().into());
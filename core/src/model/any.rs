@@ -3,6 +3,7 @@ use std::collections::BTreeSet;
 use crate::model::node_type::NodeType;
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AnyNode {
     pub nodes: BTreeSet<NodeType>,
 }
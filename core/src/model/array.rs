@@ -3,6 +3,7 @@ use crate::model::node_type::NodeType;
 use std::collections::BTreeSet;
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ArrayNode {
     pub items: Option<Box<NodeType>>,
 }
@@ -16,16 +17,12 @@ impl ArrayNode {
     }
 
     #[must_use]
-    #[allow(clippy::missing_panics_doc)]
     pub fn new_many(node_types: BTreeSet<NodeType>) -> Self {
-        match node_types.len() {
-            0 => Self { items: None },
-            1 => Self {
-                items: Some(Box::new(node_types.into_iter().next().unwrap())),
-            },
-            _ => Self {
-                items: Some(Box::new(NodeType::Any(AnyNode::new(node_types)))),
-            },
+        if node_types.len() > 1 {
+            return Self::new(NodeType::Any(AnyNode::new(node_types)));
+        }
+        Self {
+            items: node_types.into_iter().next().map(Box::new),
         }
     }
 
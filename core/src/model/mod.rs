@@ -1,3 +1,5 @@
+use serde_json::Value;
+
 pub use any::AnyNode;
 pub use array::ArrayNode;
 pub use date::DateNode;
@@ -18,7 +20,8 @@ mod number;
 mod object;
 mod string;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SchemaHypothesis {
     pub root: NodeType,
 }
@@ -27,4 +30,15 @@ impl SchemaHypothesis {
     pub fn new<N: Into<NodeType>>(root: N) -> Self {
         SchemaHypothesis { root: root.into() }
     }
+
+    /// Merges `other` into this hypothesis in place; the result is the same as the one of
+    /// [`merge_hypothesis`](crate::merge_hypothesis), without copying this hypothesis.
+    pub fn merge_in(&mut self, other: SchemaHypothesis) {
+        crate::merge::merge_node_type_in_place(&mut self.root, other.root);
+    }
+
+    /// Widens this hypothesis in place, so that it also covers `document`.
+    pub fn observe(&mut self, document: &Value) {
+        self.merge_in(crate::generate_hypothesis(document));
+    }
 }
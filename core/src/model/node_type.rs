@@ -8,6 +8,7 @@ use crate::model::object::ObjectNode;
 use crate::model::string::StringNode;
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum NodeType {
     Any(AnyNode),
     Array(ArrayNode),
@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use crate::model::node_type::NodeType;
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ObjectProperty {
     pub node_type: NodeType,
     pub required: bool,
@@ -30,6 +31,7 @@ impl ObjectProperty {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ObjectNode {
     pub properties: BTreeMap<String, ObjectProperty>,
 }
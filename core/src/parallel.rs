@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read};
+use std::num::NonZeroUsize;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::deserialize::InferredHypothesis;
+use crate::error::Error;
+use crate::merge::merge_hypothesis;
+use crate::SchemaHypothesis;
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+struct Chunk {
+    index: usize,
+    offset: usize,
+    line: usize,
+    data: Vec<u8>,
+}
+
+type ChunkResult = (usize, Result<Option<SchemaHypothesis>, Error>);
+
+/// Derives a hypothesis from all JSON documents read from `reader`, using `jobs` worker threads.
+///
+/// The input is split into chunks at line breaks, so every document has to be on a single line.
+/// The chunks are parsed on the worker threads and the resulting hypotheses are merged in input
+/// order, so the result is identical to [`generate_hypothesis_from_reader`](crate::generate_hypothesis_from_reader).
+///
+/// # Errors
+///
+/// Fails if reading the input fails, the input contains invalid JSON or no document at all.
+#[allow(clippy::missing_panics_doc)]
+pub fn generate_hypothesis_parallel<R: Read + Send>(
+    reader: R,
+    jobs: NonZeroUsize,
+) -> Result<SchemaHypothesis, Error> {
+    generate_in_chunks(reader, jobs, CHUNK_SIZE)
+}
+
+fn generate_in_chunks<R: Read + Send>(
+    reader: R,
+    jobs: NonZeroUsize,
+    chunk_size: usize,
+) -> Result<SchemaHypothesis, Error> {
+    let (chunk_sender, chunk_receiver) = mpsc::sync_channel::<Chunk>(jobs.get() * 2);
+    let chunk_receiver = Arc::new(Mutex::new(chunk_receiver));
+    let (result_sender, result_receiver) = mpsc::channel::<ChunkResult>();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.get() {
+            let chunk_receiver = Arc::clone(&chunk_receiver);
+            let result_sender = result_sender.clone();
+            scope.spawn(move || loop {
+                let chunk = match chunk_receiver.lock().unwrap().recv() {
+                    Ok(chunk) => chunk,
+                    Err(_) => return,
+                };
+                let result = (chunk.index, generate_chunk_hypothesis(&chunk));
+                if result_sender.send(result).is_err() {
+                    return;
+                }
+            });
+        }
+        drop(chunk_receiver);
+
+        scope.spawn(move || split_chunks(reader, chunk_size, &chunk_sender, &result_sender));
+
+        let result = reduce_in_order(&result_receiver);
+        // on an error, the workers and the splitter fail to send their next result and stop,
+        // instead of reading the remaining input
+        drop(result_receiver);
+        result
+    })
+}
+
+fn split_chunks<R: Read>(
+    reader: R,
+    chunk_size: usize,
+    chunk_sender: &mpsc::SyncSender<Chunk>,
+    result_sender: &Sender<ChunkResult>,
+) {
+    let mut reader = BufReader::with_capacity(chunk_size, reader);
+    let mut index = 0;
+    let mut offset = 0;
+    let mut line = 1;
+
+    loop {
+        let mut data = Vec::with_capacity(chunk_size);
+        while data.len() < chunk_size {
+            match reader.read_until(b'\n', &mut data) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(error) => {
+                    let _ = result_sender.send((index, Err(error.into())));
+                    return;
+                }
+            }
+        }
+        if data.is_empty() {
+            return;
+        }
+
+        let length = data.len();
+        let lines = data.iter().filter(|&&byte| byte == b'\n').count();
+        let chunk = Chunk {
+            index,
+            offset,
+            line,
+            data,
+        };
+        if chunk_sender.send(chunk).is_err() {
+            return;
+        }
+        index += 1;
+        offset += length;
+        line += lines;
+    }
+}
+
+fn generate_chunk_hypothesis(chunk: &Chunk) -> Result<Option<SchemaHypothesis>, Error> {
+    let mut documents =
+        serde_json::Deserializer::from_slice(&chunk.data).into_iter::<InferredHypothesis>();
+    let mut current_hypothesis: Option<SchemaHypothesis> = None;
+
+    loop {
+        let offset = documents.byte_offset();
+        let new_hypo = match documents.next() {
+            Some(Ok(InferredHypothesis(new_hypo))) => new_hypo,
+            Some(Err(error)) => {
+                let line = chunk.line + error.line() - 1;
+                // the document starts after the whitespace following the previous one
+                let whitespace = chunk.data[offset..]
+                    .iter()
+                    .take_while(|byte| byte.is_ascii_whitespace())
+                    .count();
+                let offset = chunk.offset + offset + whitespace;
+                return Err(Error::from_json(error, line, offset));
+            }
+            None => return Ok(current_hypothesis),
+        };
+        match &mut current_hypothesis {
+            None => current_hypothesis = Some(new_hypo),
+            Some(cur) => cur.merge_in(new_hypo),
+        }
+    }
+}
+
+/// Merges the chunk hypotheses in the order of their chunks, as a balanced tree: a hypothesis is
+/// merged with its left neighbour once both cover the same number of chunks, so only a
+/// logarithmic number of hypotheses is kept at a time.
+fn reduce_in_order(results: &Receiver<ChunkResult>) -> Result<SchemaHypothesis, Error> {
+    let mut pending = BTreeMap::new();
+    let mut next_index = 0;
+    let mut stack: Vec<(usize, SchemaHypothesis)> = Vec::new();
+
+    for (index, result) in results {
+        pending.insert(index, result);
+        while let Some(result) = pending.remove(&next_index) {
+            next_index += 1;
+            let mut node = match result? {
+                Some(hypothesis) => (1, hypothesis),
+                None => continue,
+            };
+            while matches!(stack.last(), Some((size, _)) if *size == node.0) {
+                let (size, left) = stack.pop().unwrap();
+                node = (size + node.0, merge_hypothesis(left, node.1));
+            }
+            stack.push(node);
+        }
+    }
+
+    let mut result: Option<SchemaHypothesis> = None;
+    while let Some((_, left)) = stack.pop() {
+        result = Some(match result {
+            None => left,
+            Some(right) => merge_hypothesis(left, right),
+        });
+    }
+    result.ok_or(Error::EmptyInput)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Write;
+    use std::io::{self, Read};
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::error::Error;
+    use crate::generate::generate_hypothesis_from_reader;
+    use crate::parallel::{generate_hypothesis_parallel, generate_in_chunks};
+
+    fn input(documents: usize) -> String {
+        let mut input = String::new();
+        for i in 0..documents {
+            let value = match i % 5 {
+                0 => format!("\"2000-01-{:02}\"", i % 28 + 1),
+                1 => "\"2000-01-01T00:00:00Z\"".to_string(),
+                2 => format!("{i}"),
+                3 => format!("[{{\"n{}\": null}}]", i % 7),
+                _ => format!("\"text {i}\""),
+            };
+            writeln!(input, "{{\"id\": {}, \"v{}\": {}}}", i, i % 11, value).unwrap();
+        }
+        input
+    }
+
+    #[test]
+    fn test_same_result_as_sequential() {
+        let input = input(2_000);
+
+        let sequential = generate_hypothesis_from_reader(input.as_bytes()).unwrap();
+        for (jobs, chunk_size) in [(1, 100), (3, 100), (8, 1000), (2, 1024 * 1024)] {
+            let jobs = NonZeroUsize::new(jobs).unwrap();
+            let parallel = generate_in_chunks(input.as_bytes(), jobs, chunk_size).unwrap();
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[test]
+    fn test_error_position() {
+        let mut input = input(1_000);
+        let offset = input.len();
+        input.push_str("\n{\"id\": }\n");
+        input.push_str(&self::input(10));
+
+        let actual = generate_in_chunks(input.as_bytes(), NonZeroUsize::new(4).unwrap(), 100);
+
+        match actual {
+            Err(Error::InvalidJson {
+                line,
+                offset: actual_offset,
+                ..
+            }) => {
+                assert_eq!(line, 1_002);
+                assert_eq!(actual_offset, offset + 1);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stops_reading_on_error() {
+        struct Counting<'a>(&'a [u8], Arc<AtomicUsize>);
+
+        impl Read for Counting<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let length = self.0.read(buf)?;
+                self.1.fetch_add(length, Ordering::SeqCst);
+                Ok(length)
+            }
+        }
+
+        let mut input = "{\"id\": 1}\n{\"id\": }\n".to_string();
+        input.push_str(&self::input(20_000));
+        let read = Arc::new(AtomicUsize::new(0));
+
+        let actual = generate_in_chunks(
+            Counting(input.as_bytes(), Arc::clone(&read)),
+            NonZeroUsize::new(4).unwrap(),
+            100,
+        );
+
+        assert!(matches!(actual, Err(Error::InvalidJson { line: 2, .. })));
+        let read = read.load(Ordering::SeqCst);
+        assert!(read < input.len() / 10, "read {} of {}", read, input.len());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let actual = generate_hypothesis_parallel("\n\n".as_bytes(), NonZeroUsize::new(2).unwrap());
+
+        assert!(matches!(actual, Err(Error::EmptyInput)));
+    }
+}
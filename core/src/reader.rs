@@ -0,0 +1,332 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde_json::de::IoRead;
+use serde_json::{StreamDeserializer, Value};
+
+use crate::error::Error;
+
+/// A JSON document read from the input, together with the byte offset it starts at.
+///
+/// Reading documents as [`InferredHypothesis`](crate::InferredHypothesis) instead of `Value` derives
+/// their hypotheses directly while parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Document<T = Value> {
+    pub value: T,
+    pub offset: usize,
+}
+
+/// Reads consecutive JSON documents from a reader.
+///
+/// A reader created with [`DocumentReader::new`] accepts documents in any layout (e.g. pretty
+/// printed), but cannot continue after an invalid document. A reader created with
+/// [`DocumentReader::ndjson`] expects one document per line and continues with the next line after
+/// an invalid one, which is then available via [`DocumentReader::current_record`].
+pub struct DocumentReader<R: Read, T = Value> {
+    mode: Mode<R, T>,
+}
+
+enum Mode<R: Read, T> {
+    Stream {
+        documents: StreamDeserializer<'static, IoRead<Tracking<R>>, T>,
+        positions: Arc<Positions>,
+        failed: bool,
+    },
+    Lines {
+        reader: BufReader<R>,
+        record: Vec<u8>,
+        line: usize,
+        offset: usize,
+    },
+}
+
+impl<R: Read, T: DeserializeOwned> DocumentReader<R, T> {
+    pub fn new(reader: R) -> Self {
+        let positions = Arc::new(Positions {
+            last_token: AtomicUsize::new(NONE),
+            start: AtomicUsize::new(NONE),
+        });
+        let reader = Tracking {
+            reader: BufReader::new(reader),
+            read: 0,
+            positions: Arc::clone(&positions),
+        };
+        DocumentReader {
+            mode: Mode::Stream {
+                documents: serde_json::Deserializer::from_reader(reader).into_iter(),
+                positions,
+                failed: false,
+            },
+        }
+    }
+
+    pub fn ndjson(reader: R) -> Self {
+        DocumentReader {
+            mode: Mode::Lines {
+                reader: BufReader::new(reader),
+                record: Vec::new(),
+                line: 0,
+                offset: 0,
+            },
+        }
+    }
+
+    /// The raw line of the most recently read document; always empty for readers that are not
+    /// line based.
+    #[must_use]
+    pub fn current_record(&self) -> &[u8] {
+        match &self.mode {
+            Mode::Stream { .. } => &[],
+            Mode::Lines { record, .. } => record,
+        }
+    }
+}
+
+impl<R: Read, T: DeserializeOwned> Iterator for DocumentReader<R, T> {
+    type Item = Result<Document<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.mode {
+            Mode::Stream {
+                documents,
+                positions,
+                failed,
+            } => {
+                if *failed {
+                    return None;
+                }
+                let end = documents.byte_offset();
+                positions.expect_document(end);
+                let result = documents.next()?;
+                // the documents are separated by whitespace, if there is nothing else, the input
+                // ended unexpectedly
+                let offset = positions.start().unwrap_or(end);
+                let result = result.map_err(|error| {
+                    *failed = true;
+                    let line = error.line();
+                    Error::from_json(error, line, offset)
+                });
+                Some(result.map(|value| Document { value, offset }))
+            }
+            Mode::Lines {
+                reader,
+                record,
+                line,
+                offset,
+            } => loop {
+                *offset += record.len();
+                record.clear();
+                match reader.read_until(b'\n', record) {
+                    Ok(0) => return None,
+                    Ok(_) => *line += 1,
+                    Err(error) => return Some(Err(error.into())),
+                }
+                if record.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                let result = serde_json::from_slice(record)
+                    .map(|value| Document {
+                        value,
+                        offset: *offset,
+                    })
+                    .map_err(|error| Error::from_json(error, *line, *offset));
+                return Some(result);
+            },
+        }
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+/// Passes the input on to the deserializer, which reads it byte by byte, and records where the
+/// documents start, as the deserializer only knows where the previous one ends.
+struct Tracking<R: Read> {
+    reader: BufReader<R>,
+    /// The number of bytes the deserializer has read.
+    read: usize,
+    positions: Arc<Positions>,
+}
+
+/// Shared between the reader and the deserializer reading from it.
+struct Positions {
+    /// The offset of the last byte read that is no whitespace.
+    last_token: AtomicUsize,
+    /// The offset of the first byte read since [`Positions::expect_document`] that is no
+    /// whitespace, i.e. where the next document starts.
+    start: AtomicUsize,
+}
+
+impl Positions {
+    /// Starts looking for the next document after `end`, the end of the previous one. The
+    /// deserializer may have read a byte beyond it already, to find the end of the previous one.
+    fn expect_document(&self, end: usize) {
+        let start = if self.last_token.load(Ordering::Relaxed) == end {
+            end
+        } else {
+            NONE
+        };
+        self.start.store(start, Ordering::Relaxed);
+    }
+
+    fn start(&self) -> Option<usize> {
+        match self.start.load(Ordering::Relaxed) {
+            NONE => None,
+            start => Some(start),
+        }
+    }
+}
+
+impl<R: Read> Read for Tracking<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.reader.read(buf)?;
+        let read = self.read;
+        self.read += length;
+        if let Some(index) = buf[..length]
+            .iter()
+            .rposition(|byte| !byte.is_ascii_whitespace())
+        {
+            if self.positions.start.load(Ordering::Relaxed) == NONE {
+                let first = buf[..length]
+                    .iter()
+                    .position(|byte| !byte.is_ascii_whitespace())
+                    .unwrap_or(index);
+                self.positions.start.store(read + first, Ordering::Relaxed);
+            }
+            self.positions
+                .last_token
+                .store(read + index, Ordering::Relaxed);
+        }
+        Ok(length)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use crate::error::Error;
+    use crate::reader::{Document, DocumentReader};
+    use crate::{generate_hypothesis, InferredHypothesis};
+
+    #[test]
+    fn test_pretty_printed_documents() {
+        let input = "{\n  \"a\": 1\n}\n[\n  true\n]\n";
+
+        let documents: Vec<Document> = DocumentReader::new(input.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            documents,
+            vec![
+                Document {
+                    value: json!({"a": 1}),
+                    offset: 0
+                },
+                Document {
+                    value: json!([true]),
+                    offset: 13
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offsets_skip_whitespace() {
+        let input = "  {\"a\": 1}{\"b\": 2}\n\n 3 4\t\"x\"";
+
+        let offsets: Vec<usize> = DocumentReader::<_, Value>::new(input.as_bytes())
+            .map(|document| document.unwrap().offset)
+            .collect();
+
+        assert_eq!(offsets, vec![2, 10, 21, 23, 25]);
+    }
+
+    #[test]
+    fn test_stops_at_invalid_document() {
+        let input = "{\"a\": 1}\n{\"a\": }\n{\"a\": 3}\n";
+
+        let results: Vec<Result<Document, Error>> = DocumentReader::new(input.as_bytes()).collect();
+
+        assert_eq!(results.len(), 2);
+        match &results[1] {
+            Err(Error::InvalidJson {
+                line,
+                column,
+                offset,
+                ..
+            }) => assert_eq!((*line, *column, *offset), (2, 7, 9)),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ndjson_continues_after_invalid_line() {
+        let input = "{\"a\": 1}\n\n{\"a\": }\n{\"a\": 3}";
+        let mut reader = DocumentReader::ndjson(input.as_bytes());
+
+        assert_eq!(
+            reader.next().unwrap().unwrap(),
+            Document {
+                value: json!({"a": 1}),
+                offset: 0
+            }
+        );
+        match reader.next().unwrap() {
+            Err(Error::InvalidJson {
+                line,
+                column,
+                offset,
+                ..
+            }) => assert_eq!((line, column, offset), (3, 7, 10)),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(reader.current_record(), b"{\"a\": }\n");
+        assert_eq!(
+            reader.next().unwrap().unwrap(),
+            Document {
+                value: json!({"a": 3}),
+                offset: 18
+            }
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_read_hypotheses() {
+        let input = "{\"a\": 1}\n[\"x\"]";
+
+        let hypotheses: Vec<Document<InferredHypothesis>> = DocumentReader::new(input.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            hypotheses,
+            vec![
+                Document {
+                    value: InferredHypothesis(generate_hypothesis(&json!({"a": 1}))),
+                    offset: 0
+                },
+                Document {
+                    value: InferredHypothesis(generate_hypothesis(&json!(["x"]))),
+                    offset: 9
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_message() {
+        let error = DocumentReader::<_, Value>::ndjson("1\n\n[1,]".as_bytes())
+            .nth(1)
+            .unwrap()
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "invalid JSON at line 3, column 4 (document starting at byte offset 3): trailing comma"
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{json, Map, Value};
+
+use crate::merge::merge_node_type;
+use crate::model::{AnyNode, ArrayNode, NodeType, ObjectNode, ObjectProperty};
+use crate::renderer::naming::{ascii, TypeNames};
+use crate::SchemaHypothesis;
+
+/// The type of the items of arrays and the values of maps that have always been empty.
+const UNKNOWN: &str = "string";
+
+/// The property holding the type of the values of an object rendered as map.
+const MAP_VALUES: &str = "values";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AvroOptions {
+    /// The name of the root record; nested records are named after their properties.
+    pub root_name: String,
+    /// The namespace of all records.
+    pub namespace: Option<String>,
+    /// Paths of objects rendered as maps instead of records, e.g. `$.labels`, for objects with
+    /// arbitrary keys; the values of a map have the merged type of all properties of the object.
+    pub map_paths: BTreeSet<String>,
+}
+
+impl Default for AvroOptions {
+    fn default() -> Self {
+        AvroOptions {
+            root_name: "Root".to_string(),
+            namespace: None,
+            map_paths: BTreeSet::new(),
+        }
+    }
+}
+
+/// An Avro schema and the unions that could not be expressed exactly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AvroSchema {
+    /// The schema, as expected in an `.avsc` file.
+    pub schema: Value,
+    /// A warning for every union that was reduced, with the path of its property.
+    pub warnings: Vec<String>,
+}
+
+/// Renders an Avro schema (`.avsc`) with a record for every object.
+///
+/// Unions that Avro cannot express, because they contain several types of the same kind (e.g. an
+/// integer and a date-time, which are both a `long` in Avro), are reduced to the first of these
+/// types with a warning: a date-time then is a plain `long` without its logical type.
+#[must_use]
+pub fn render_avro(schema: &SchemaHypothesis, options: &AvroOptions) -> AvroSchema {
+    let root = with_maps(&schema.root, "$", &options.map_paths);
+    // Avro names consist of ASCII letters, digits and underscores
+    let type_names = TypeNames::ascii(&root, &options.root_name);
+    let mut renderer = AvroRenderer {
+        type_names: &type_names,
+        options,
+        defined: BTreeSet::new(),
+        warnings: Vec::new(),
+    };
+    let schema = renderer.render_node(&root, "$");
+    AvroSchema {
+        schema,
+        warnings: renderer.warnings,
+    }
+}
+
+/// Replaces the objects at `map_paths` by objects with the single property [`MAP_VALUES`], whose
+/// type is the merged type of all properties; this way, the values are named like any other
+/// object.
+fn with_maps(node_type: &NodeType, path: &str, map_paths: &BTreeSet<String>) -> NodeType {
+    match node_type {
+        NodeType::Object(object) if map_paths.contains(path) => {
+            let values = object
+                .properties
+                .values()
+                .map(|property| with_maps(&property.node_type, &format!("{path}.*"), map_paths))
+                .reduce(merge_node_type);
+            let properties = values
+                .map(|values| (MAP_VALUES.to_string(), ObjectProperty::new(values)))
+                .into_iter()
+                .collect();
+            ObjectNode::new(properties).into()
+        }
+        NodeType::Object(object) => {
+            let properties: BTreeMap<String, ObjectProperty> = object
+                .properties
+                .iter()
+                .map(|(key, property)| {
+                    let node_type =
+                        with_maps(&property.node_type, &format!("{path}.{key}"), map_paths);
+                    let property = ObjectProperty {
+                        node_type,
+                        required: property.required,
+                    };
+                    (key.clone(), property)
+                })
+                .collect();
+            ObjectNode::new(properties).into()
+        }
+        NodeType::Array(ArrayNode { items: Some(items) }) => {
+            ArrayNode::new(with_maps(items, &format!("{path}[*]"), map_paths)).into()
+        }
+        NodeType::Any(any) => {
+            let nodes = any
+                .nodes
+                .iter()
+                .map(|node_type| with_maps(node_type, path, map_paths))
+                .collect();
+            AnyNode::new(nodes).into()
+        }
+        node_type => node_type.clone(),
+    }
+}
+
+struct AvroRenderer<'a> {
+    type_names: &'a TypeNames<'a>,
+    options: &'a AvroOptions,
+    /// Records are defined where they are used first and referenced by name afterwards.
+    defined: BTreeSet<String>,
+    warnings: Vec<String>,
+}
+
+impl AvroRenderer<'_> {
+    fn render_node(&mut self, node_type: &NodeType, path: &str) -> Value {
+        match node_type {
+            NodeType::String(_) => json!("string"),
+            NodeType::Date(_) => json!({"type": "int", "logicalType": "date"}),
+            NodeType::DateTime(_) => json!({"type": "long", "logicalType": "timestamp-millis"}),
+            NodeType::Integer(_) => json!("long"),
+            NodeType::Number(_) => json!("double"),
+            NodeType::Boolean => json!("boolean"),
+            NodeType::Null => json!("null"),
+            NodeType::Array(array) => {
+                let items = match &array.items {
+                    Some(items) => self.render_node(items, &format!("{path}[*]")),
+                    None => json!(UNKNOWN),
+                };
+                json!({"type": "array", "items": items})
+            }
+            NodeType::Object(object) if self.options.map_paths.contains(path) => {
+                let values = match object.properties.get(MAP_VALUES) {
+                    Some(values) => self.render_node(&values.node_type, &format!("{path}.*")),
+                    None => json!(UNKNOWN),
+                };
+                json!({"type": "map", "values": values})
+            }
+            NodeType::Object(object) => self.render_record(object, path),
+            NodeType::Any(any) => {
+                let members: Vec<Value> = any
+                    .nodes
+                    .iter()
+                    .map(|node_type| self.render_node(node_type, path))
+                    .collect();
+                self.union(members, path)
+            }
+        }
+    }
+
+    fn render_record(&mut self, object: &ObjectNode, path: &str) -> Value {
+        let name = self.type_names.name(object).to_string();
+        let full_name = match &self.options.namespace {
+            Some(namespace) => format!("{namespace}.{name}"),
+            None => name.clone(),
+        };
+        if !self.defined.insert(name.clone()) {
+            return json!(full_name);
+        }
+
+        let mut field_names = BTreeSet::new();
+        let fields: Vec<Value> = object
+            .properties
+            .iter()
+            .map(|(key, property)| {
+                let path = format!("{path}.{key}");
+                let node_type = self.render_node(&property.node_type, &path);
+                let mut field = Map::new();
+                let mut field_name = field_name(key);
+                let mut suffix = 2;
+                while field_names.contains(&field_name) {
+                    field_name = format!("{}_{suffix}", self::field_name(key));
+                    suffix += 1;
+                }
+                field_names.insert(field_name.clone());
+                if field_name != *key {
+                    field.insert("doc".to_string(), json!(format!("JSON property `{key}`")));
+                }
+                field.insert("name".to_string(), json!(field_name));
+                if property.required {
+                    field.insert("type".to_string(), node_type);
+                } else {
+                    field.insert(
+                        "type".to_string(),
+                        self.union(vec![json!("null"), node_type], &path),
+                    );
+                    field.insert("default".to_string(), Value::Null);
+                }
+                Value::Object(field)
+            })
+            .collect();
+
+        let mut record = Map::new();
+        record.insert("type".to_string(), json!("record"));
+        record.insert("name".to_string(), json!(name));
+        if let Some(namespace) = &self.options.namespace {
+            record.insert("namespace".to_string(), json!(namespace));
+        }
+        record.insert("fields".to_string(), Value::Array(fields));
+        Value::Object(record)
+    }
+
+    /// Flattens `members` into a single union, with `null` first (as required for a `null`
+    /// default), and drops members of a kind that is already part of the union, with a warning
+    /// if they differ from the member kept.
+    fn union(&mut self, members: Vec<Value>, path: &str) -> Value {
+        let mut kinds: BTreeMap<String, Value> = BTreeMap::new();
+        let mut union = Vec::new();
+        let members = members.into_iter().flat_map(|member| match member {
+            Value::Array(members) => members,
+            member => vec![member],
+        });
+        for member in members {
+            match kinds.get(&kind(&member)) {
+                None => {
+                    kinds.insert(kind(&member), member.clone());
+                    union.push(member);
+                }
+                Some(kept) if *kept != member => self.warnings.push(format!(
+                    "{path}: {} and {} cannot be part of the same union, reduced to {}",
+                    describe(kept),
+                    describe(&member),
+                    describe(kept)
+                )),
+                Some(_) => {}
+            }
+        }
+        union.sort_by_key(|member| *member != json!("null"));
+
+        if union.len() == 1 {
+            union.remove(0)
+        } else {
+            Value::Array(union)
+        }
+    }
+}
+
+/// Avro does not allow two types of the same kind in a union, except for records with different
+/// names.
+fn kind(schema: &Value) -> String {
+    match schema {
+        Value::String(name) => name.clone(),
+        Value::Object(map) => match map.get("type") {
+            Some(Value::String(kind)) if kind == "record" => {
+                format!("record {}", map.get("name").unwrap_or(&Value::Null))
+            }
+            Some(Value::String(kind)) => kind.clone(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+/// The logical type of `schema`, if any, or its kind.
+fn describe(schema: &Value) -> String {
+    match schema.get("logicalType") {
+        Some(Value::String(logical_type)) => logical_type.clone(),
+        _ => kind(schema),
+    }
+}
+
+/// Avro names consist of ASCII letters, digits and underscores and do not start with a digit.
+fn field_name(key: &str) -> String {
+    let name: String = ascii(key)
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.chars().next() {
+        None => "_".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("_{name}"),
+        Some(_) => name,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::btreeset;
+    use serde_json::json;
+
+    use crate::renderer::avro_renderer::{render_avro, AvroOptions};
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_records() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "id": 1,
+                "day": "2022-01-01",
+                "created-at": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x"},
+                "tags": ["a"]
+            })),
+            generate_hypothesis(&json!({
+                "id": 2,
+                "day": "2022-01-01",
+                "created-at": "2022-01-01T00:00:00Z",
+                "tags": [],
+                "seller": {"name": "y"}
+            })),
+        );
+        let options = AvroOptions {
+            root_name: "Order".to_string(),
+            namespace: Some("com.example".to_string()),
+            ..AvroOptions::default()
+        };
+
+        let actual = render_avro(&hypothesis, &options);
+
+        assert!(actual.warnings.is_empty());
+        assert_eq!(
+            actual.schema,
+            json!({
+                "type": "record",
+                "name": "Order",
+                "namespace": "com.example",
+                "fields": [
+                    {
+                        "name": "created_at",
+                        "doc": "JSON property `created-at`",
+                        "type": {"type": "long", "logicalType": "timestamp-millis"}
+                    },
+                    {
+                        "name": "customer",
+                        "type": [
+                            "null",
+                            {
+                                "type": "record",
+                                "name": "Customer",
+                                "namespace": "com.example",
+                                "fields": [{"name": "name", "type": "string"}]
+                            }
+                        ],
+                        "default": null
+                    },
+                    {"name": "day", "type": {"type": "int", "logicalType": "date"}},
+                    {"name": "id", "type": "long"},
+                    {
+                        "name": "seller",
+                        "type": [
+                            "null",
+                            {
+                                "type": "record",
+                                "name": "Seller",
+                                "namespace": "com.example",
+                                "fields": [{"name": "name", "type": "string"}]
+                            }
+                        ],
+                        "default": null
+                    },
+                    {"name": "tags", "type": {"type": "array", "items": "string"}}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_unions() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({"a": null, "b": 1, "c": [{"x": 1}, "x", 1]})),
+            generate_hypothesis(&json!({"a": "x", "b": "2022-01-01T00:00:00Z"})),
+        );
+
+        let actual = render_avro(&hypothesis, &AvroOptions::default());
+
+        assert_eq!(
+            actual.schema["fields"],
+            json!([
+                {"name": "a", "type": ["null", "string"]},
+                {"name": "b", "type": "long"},
+                {
+                    "name": "c",
+                    "type": [
+                        "null",
+                        {
+                            "type": "array",
+                            "items": [
+                                "long",
+                                {
+                                    "type": "record",
+                                    "name": "CItem",
+                                    "fields": [{"name": "x", "type": "long"}]
+                                },
+                                "string"
+                            ]
+                        }
+                    ],
+                    "default": null
+                }
+            ])
+        );
+        assert_eq!(
+            actual.warnings,
+            vec![
+                "$.b: long and timestamp-millis cannot be part of the same union, reduced to long"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ascii_names() {
+        let hypothesis = generate_hypothesis(&json!({"größe": {"wert": 1}}));
+
+        let actual = render_avro(&hypothesis, &AvroOptions::default());
+
+        assert_eq!(actual.schema["fields"][0]["name"], json!("groesse"));
+        assert_eq!(actual.schema["fields"][0]["type"]["name"], json!("Groesse"));
+    }
+
+    #[test]
+    fn test_maps() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({"labels": {"team": "a", "env": {"stage": "x"}}})),
+            generate_hypothesis(&json!({"labels": {"cost-center": null}})),
+        );
+        let options = AvroOptions {
+            map_paths: btreeset!["$.labels".to_string()],
+            ..AvroOptions::default()
+        };
+
+        let actual = render_avro(&hypothesis, &options);
+
+        assert_eq!(
+            actual.schema["fields"],
+            json!([{
+                "name": "labels",
+                "type": {
+                    "type": "map",
+                    "values": [
+                        "null",
+                        {
+                            "type": "record",
+                            "name": "Values",
+                            "fields": [{"name": "stage", "type": "string"}]
+                        },
+                        "string"
+                    ]
+                }
+            }])
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::BTreeSet;
+
+use serde_json::{json, Map, Value};
+
+use crate::model::{AnyNode, NodeType, ObjectNode};
+use crate::SchemaHypothesis;
+
+/// Prefixes of column names that BigQuery reserves, in any case.
+const RESERVED_PREFIXES: &[&str] = &[
+    "_TABLE_",
+    "_FILE_",
+    "_PARTITION",
+    "_ROW_TIMESTAMP",
+    "__ROOT__",
+    "_COLIDENTIFIER",
+];
+
+/// The type of the columns whose type BigQuery cannot express.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BigQueryFallback {
+    /// `JSON`, which accepts any value.
+    Json,
+    /// `STRING`, which accepts any scalar value, but no objects or arrays.
+    String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigQueryOptions {
+    pub fallback: BigQueryFallback,
+}
+
+impl Default for BigQueryOptions {
+    fn default() -> Self {
+        BigQueryOptions {
+            fallback: BigQueryFallback::Json,
+        }
+    }
+}
+
+/// A BigQuery table schema and the properties that could not be expressed exactly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BigQuerySchema {
+    /// The fields, as expected by `bq mk --schema` or `bq load --schema`.
+    pub fields: Value,
+    /// A warning for every column that falls back to [`BigQueryOptions::fallback`] or was
+    /// renamed, with the path of its property.
+    pub warnings: Vec<String>,
+}
+
+/// Renders the schema of a BigQuery table the documents can be loaded into as newline-delimited
+/// JSON, with a `RECORD` for every nested object.
+///
+/// Properties of varying types, arrays of arrays or of `null` and objects without properties
+/// have no BigQuery type; they fall back to [`BigQueryOptions::fallback`] with a warning.
+#[must_use]
+pub fn render_bigquery(schema: &SchemaHypothesis, options: &BigQueryOptions) -> BigQuerySchema {
+    let mut renderer = BigQueryRenderer {
+        options,
+        warnings: Vec::new(),
+    };
+    let fields = match &schema.root {
+        NodeType::Object(root) => renderer.render_fields(root, "$"),
+        root => {
+            renderer
+                .warnings
+                .push("$: the documents are no objects, so they are the column `value`".into());
+            vec![renderer.render_field("value", root, true, "$")]
+        }
+    };
+    BigQuerySchema {
+        fields: Value::Array(fields),
+        warnings: renderer.warnings,
+    }
+}
+
+struct BigQueryRenderer<'a> {
+    options: &'a BigQueryOptions,
+    warnings: Vec<String>,
+}
+
+impl BigQueryRenderer<'_> {
+    fn render_fields(&mut self, object: &ObjectNode, path: &str) -> Vec<Value> {
+        // column names are case-insensitive
+        let mut names = BTreeSet::new();
+        object
+            .properties
+            .iter()
+            .map(|(key, property)| {
+                let path = format!("{path}.{key}");
+                let mut name = column_name(key);
+                let mut suffix = 2;
+                while names.contains(&name.to_lowercase()) {
+                    name = format!("{}_{suffix}", column_name(key));
+                    suffix += 1;
+                }
+                names.insert(name.to_lowercase());
+                if name != *key {
+                    self.warnings
+                        .push(format!("{path}: renamed to the column `{name}`"));
+                }
+                self.render_field(&name, &property.node_type, property.required, &path)
+            })
+            .collect()
+    }
+
+    fn render_field(
+        &mut self,
+        name: &str,
+        node_type: &NodeType,
+        required: bool,
+        path: &str,
+    ) -> Value {
+        let (mode, column_type) = match node_type {
+            NodeType::Array(array) => match &array.items {
+                Some(items) => match self.column_type(items, &format!("{path}[*]")) {
+                    Some(column_type) => ("REPEATED", column_type),
+                    None => {
+                        let reason = "the items are arrays, `null` or of varying types";
+                        (mode(required), self.fallback(path, reason))
+                    }
+                },
+                None => {
+                    let reason = "the items are unknown, as the array has always been empty";
+                    ("REPEATED", self.fallback(path, reason))
+                }
+            },
+            NodeType::Any(any) if any.nodes.contains(&NodeType::Null) => {
+                let types: BTreeSet<NodeType> = any
+                    .nodes
+                    .iter()
+                    .filter(|node_type| **node_type != NodeType::Null)
+                    .cloned()
+                    .collect();
+                let node_type = match types.len() {
+                    1 => types.into_iter().next().unwrap_or(NodeType::Null),
+                    _ => NodeType::Any(AnyNode::new(types)),
+                };
+                return self.render_field(name, &node_type, false, path);
+            }
+            node_type => {
+                let column_type = self.column_type(node_type, path);
+                let column_type = match column_type {
+                    Some(column_type) => column_type,
+                    None => self.fallback(path, reason(node_type)),
+                };
+                (mode(required && *node_type != NodeType::Null), column_type)
+            }
+        };
+
+        let mut field = Map::new();
+        field.insert("name".to_string(), json!(name));
+        match column_type {
+            ColumnType::Scalar(column_type) => {
+                field.insert("type".to_string(), json!(column_type));
+            }
+            ColumnType::Record(fields) => {
+                field.insert("type".to_string(), json!("RECORD"));
+                field.insert("fields".to_string(), Value::Array(fields));
+            }
+        }
+        field.insert("mode".to_string(), json!(mode));
+        Value::Object(field)
+    }
+
+    /// The type of a single value of `node_type`, if BigQuery can express it.
+    fn column_type(&mut self, node_type: &NodeType, path: &str) -> Option<ColumnType> {
+        let column_type = match node_type {
+            NodeType::String(_) => "STRING",
+            NodeType::Date(_) => "DATE",
+            NodeType::DateTime(_) => "TIMESTAMP",
+            NodeType::Integer(_) => "INTEGER",
+            NodeType::Number(_) => "FLOAT",
+            NodeType::Boolean => "BOOLEAN",
+            NodeType::Object(object) if !object.properties.is_empty() => {
+                return Some(ColumnType::Record(self.render_fields(object, path)));
+            }
+            NodeType::Any(any)
+                if any.nodes.iter().all(|node_type| {
+                    matches!(node_type, NodeType::Integer(_) | NodeType::Number(_))
+                }) =>
+            {
+                "FLOAT"
+            }
+            _ => return None,
+        };
+        Some(ColumnType::Scalar(column_type))
+    }
+
+    fn fallback(&mut self, path: &str, reason: &str) -> ColumnType {
+        let column_type = match self.options.fallback {
+            BigQueryFallback::Json => "JSON",
+            BigQueryFallback::String => "STRING",
+        };
+        self.warnings
+            .push(format!("{path}: {reason}, falling back to {column_type}"));
+        ColumnType::Scalar(column_type)
+    }
+}
+
+enum ColumnType {
+    Scalar(&'static str),
+    Record(Vec<Value>),
+}
+
+fn mode(required: bool) -> &'static str {
+    if required {
+        "REQUIRED"
+    } else {
+        "NULLABLE"
+    }
+}
+
+fn reason(node_type: &NodeType) -> &'static str {
+    match node_type {
+        NodeType::Null => "the type is unknown, as it has always been `null`",
+        NodeType::Object(_) => "the object has no properties",
+        _ => "the types vary",
+    }
+}
+
+/// BigQuery column names consist of letters, digits and underscores, do not start with a digit
+/// and do not start with one of the [`RESERVED_PREFIXES`].
+fn column_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let upper = name.to_uppercase();
+    match name.chars().next() {
+        None => "_".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("_{name}"),
+        Some(_)
+            if RESERVED_PREFIXES
+                .iter()
+                .any(|prefix| upper.starts_with(prefix)) =>
+        {
+            format!("column{name}")
+        }
+        Some(_) => name,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::bigquery_renderer::{render_bigquery, BigQueryFallback, BigQueryOptions};
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_fields() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "id": 1,
+                "created": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x", "since": "2022-01-01"},
+                "tags": ["a"],
+                "score": 1,
+                "note": null
+            })),
+            generate_hypothesis(&json!({
+                "id": 2,
+                "created": "2022-01-01T00:00:00Z",
+                "tags": [],
+                "score": 1.5,
+                "note": "x"
+            })),
+        );
+
+        let actual = render_bigquery(&hypothesis, &BigQueryOptions::default());
+
+        assert_eq!(
+            actual.fields,
+            json!([
+                {"name": "created", "type": "TIMESTAMP", "mode": "REQUIRED"},
+                {
+                    "name": "customer",
+                    "type": "RECORD",
+                    "fields": [
+                        {"name": "name", "type": "STRING", "mode": "REQUIRED"},
+                        {"name": "since", "type": "DATE", "mode": "REQUIRED"}
+                    ],
+                    "mode": "NULLABLE"
+                },
+                {"name": "id", "type": "INTEGER", "mode": "REQUIRED"},
+                {"name": "note", "type": "STRING", "mode": "NULLABLE"},
+                {"name": "score", "type": "FLOAT", "mode": "REQUIRED"},
+                {"name": "tags", "type": "STRING", "mode": "REPEATED"}
+            ])
+        );
+        assert!(actual.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_fallback() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(
+                &json!({"id": 1, "list": [[1]], "empty": [], "a-b": {}, "n": null, "_file_name": "f"}),
+            ),
+            generate_hypothesis(
+                &json!({"id": "x", "list": [], "empty": [], "a-b": {}, "n": null, "_file_name": "g"}),
+            ),
+        );
+        let options = BigQueryOptions {
+            fallback: BigQueryFallback::String,
+        };
+
+        let actual = render_bigquery(&hypothesis, &options);
+
+        assert_eq!(
+            actual.fields,
+            json!([
+                {"name": "column_file_name", "type": "STRING", "mode": "REQUIRED"},
+                {"name": "a_b", "type": "STRING", "mode": "REQUIRED"},
+                {"name": "empty", "type": "STRING", "mode": "REPEATED"},
+                {"name": "id", "type": "STRING", "mode": "REQUIRED"},
+                {"name": "list", "type": "STRING", "mode": "REQUIRED"},
+                {"name": "n", "type": "STRING", "mode": "NULLABLE"}
+            ])
+        );
+        assert_eq!(
+            actual.warnings,
+            vec![
+                "$._file_name: renamed to the column `column_file_name`",
+                "$.a-b: renamed to the column `a_b`",
+                "$.a-b: the object has no properties, falling back to STRING",
+                "$.empty: the items are unknown, as the array has always been empty, falling back to STRING",
+                "$.id: the types vary, falling back to STRING",
+                "$.list: the items are arrays, `null` or of varying types, falling back to STRING",
+                "$.n: the type is unknown, as it has always been `null`, falling back to STRING"
+            ]
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::model::{NodeType, ObjectNode};
+use crate::renderer::naming::{pascal_case, TypeNames};
+use crate::SchemaHypothesis;
+
+/// Words written in capitals in Go names, as recommended by the Go code review comments.
+const INITIALISMS: &[&str] = &[
+    "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS", "ID", "IP",
+    "JSON", "LHS", "QPS", "RAM", "RHS", "RPC", "SKU", "SLA", "SMTP", "SQL", "SSH", "TCP", "TLS",
+    "TTL", "UDP", "UI", "UID", "URI", "URL", "UTF8", "UUID", "VAT", "VM", "XML", "XMPP", "XSRF",
+    "XSS",
+];
+
+/// Renders Go source declaring a struct with `json` tags for the root type (named `root_name`)
+/// and for every nested object, named after the property it is found at, in package `package`.
+///
+/// Optional properties are tagged `omitempty`; nullable properties and optional structs (also
+/// date-times) are pointers. Properties of varying types are `any`, or `json.RawMessage` to be decoded later if
+/// they may be an object or an array.
+#[must_use]
+pub fn render_go(schema: &SchemaHypothesis, root_name: &str, package: &str) -> String {
+    let type_names = TypeNames::new(&schema.root, root_name);
+    let mut renderer = GoRenderer {
+        type_names: &type_names,
+        imports: BTreeSet::new(),
+    };
+
+    let mut declarations = Vec::new();
+    if !schema.root.is_object() {
+        let root_type = renderer.type_of(&schema.root);
+        declarations.push(format!("type {} {root_type}\n", type_names.root_name()));
+    }
+    for (name, object) in type_names.objects() {
+        declarations.push(renderer.render_struct(name, object));
+    }
+
+    let mut header = format!("package {package}\n");
+    match renderer.imports.len() {
+        0 => {}
+        1 => {
+            let import = renderer.imports.iter().next().unwrap();
+            let _ = write!(header, "\nimport \"{import}\"\n");
+        }
+        _ => {
+            header.push_str("\nimport (\n");
+            for import in &renderer.imports {
+                let _ = writeln!(header, "\t\"{import}\"");
+            }
+            header.push_str(")\n");
+        }
+    }
+    declarations.insert(0, header);
+    declarations.join("\n")
+}
+
+struct GoRenderer<'a> {
+    type_names: &'a TypeNames<'a>,
+    /// The packages imported.
+    imports: BTreeSet<&'static str>,
+}
+
+impl GoRenderer<'_> {
+    fn render_struct(&mut self, name: &str, object: &ObjectNode) -> String {
+        // (comment, field, type, tag), aligned in columns as by gofmt
+        let mut fields = Vec::new();
+        let mut field_names = BTreeSet::new();
+        for (key, property) in &object.properties {
+            let mut field = go_name(key);
+            let mut suffix = 2;
+            while field_names.contains(&field) {
+                field = format!("{}{suffix}", go_name(key));
+                suffix += 1;
+            }
+            field_names.insert(field.clone());
+
+            let mut field_type = self.type_of(&property.node_type);
+            if !property.required
+                && matches!(
+                    property.node_type,
+                    NodeType::Object(_) | NodeType::DateTime(_)
+                )
+            {
+                // `omitempty` does not omit structs, `time.Time` included
+                field_type = format!("*{field_type}");
+            }
+            let mut comment = if date_format(&property.node_type) {
+                Some("// ISO 8601 date, e.g. `2022-01-31`".to_string())
+            } else {
+                None
+            };
+            let tag = if is_tag_name(key) {
+                let omit_empty = if property.required { "" } else { ",omitempty" };
+                format!("`json:\"{key}{omit_empty}\"`")
+            } else {
+                comment = Some(format!(
+                    "// JSON property {} cannot be named in a struct tag",
+                    serde_json::Value::from(key.as_str())
+                ));
+                "`json:\"-\"`".to_string()
+            };
+            fields.push((comment, field, field_type, tag));
+        }
+
+        let name_width = fields.iter().map(|(_, f, _, _)| f.chars().count()).max();
+        let type_width = fields.iter().map(|(_, _, t, _)| t.chars().count()).max();
+        let mut declaration = format!("type {name} struct {{\n");
+        for (comment, field, field_type, tag) in &fields {
+            if let Some(comment) = comment {
+                let _ = writeln!(declaration, "\t{comment}");
+            }
+            let _ = writeln!(
+                declaration,
+                "\t{field:name_width$} {field_type:type_width$} {tag}",
+                name_width = name_width.unwrap_or_default(),
+                type_width = type_width.unwrap_or_default()
+            );
+        }
+        declaration.push_str("}\n");
+        declaration
+    }
+
+    fn type_of(&mut self, node_type: &NodeType) -> String {
+        match node_type {
+            NodeType::String(_) | NodeType::Date(_) => "string".to_string(),
+            NodeType::DateTime(_) => {
+                self.imports.insert("time");
+                "time.Time".to_string()
+            }
+            NodeType::Integer(_) => "int64".to_string(),
+            NodeType::Number(_) => "float64".to_string(),
+            NodeType::Boolean => "bool".to_string(),
+            NodeType::Null => "any".to_string(),
+            NodeType::Array(array) => match &array.items {
+                Some(items) => format!("[]{}", self.type_of(items)),
+                None => "[]any".to_string(),
+            },
+            NodeType::Object(object) => self.type_names.name(object).to_string(),
+            NodeType::Any(any) => {
+                let types: Vec<&NodeType> = any
+                    .nodes
+                    .iter()
+                    .filter(|node_type| **node_type != NodeType::Null)
+                    .collect();
+                match types.as_slice() {
+                    [] => "any".to_string(),
+                    [single] => {
+                        let single = self.type_of(single);
+                        if is_nilable(&single) {
+                            single
+                        } else {
+                            format!("*{single}")
+                        }
+                    }
+                    [NodeType::Integer(_), NodeType::Number(_)]
+                        if !any.nodes.contains(&NodeType::Null) =>
+                    {
+                        "float64".to_string()
+                    }
+                    [NodeType::Integer(_), NodeType::Number(_)] => "*float64".to_string(),
+                    _ if types
+                        .iter()
+                        .any(|node_type| node_type.is_object() || node_type.is_array()) =>
+                    {
+                        self.imports.insert("encoding/json");
+                        "json.RawMessage".to_string()
+                    }
+                    _ => "any".to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Types that are `nil` for a JSON `null` already, without a pointer.
+fn is_nilable(go_type: &str) -> bool {
+    go_type.starts_with("[]")
+        || go_type.starts_with('*')
+        || go_type == "any"
+        || go_type == "json.RawMessage"
+}
+
+fn date_format(node_type: &NodeType) -> bool {
+    match node_type {
+        NodeType::Date(_) => true,
+        NodeType::Array(array) => array.items.as_deref().is_some_and(date_format),
+        NodeType::Any(any) => any.nodes.iter().any(date_format),
+        _ => false,
+    }
+}
+
+/// An exported Go name for `key`, e.g. `OrderID` for `orderId`. Names starting with a letter
+/// without case, which Go does not export, are prefixed with `X`, e.g. `X日本` for `日本`.
+fn go_name(key: &str) -> String {
+    let name = pascal_case(key);
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    words.push(word);
+    let name: String = words
+        .into_iter()
+        .map(|word| {
+            let upper = word.to_uppercase();
+            if INITIALISMS.contains(&upper.as_str()) {
+                upper
+            } else {
+                word
+            }
+        })
+        .collect();
+    if name.starts_with(char::is_uppercase) {
+        name
+    } else {
+        format!("X{name}")
+    }
+}
+
+/// Whether `key` can be the name in a `json` struct tag, which `encoding/json` restricts to
+/// letters, digits and some punctuation.
+fn is_tag_name(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || "!#$%&()*+-./:;<=>?@[]^_{|}~ ".contains(c))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::go_renderer::{go_name, render_go};
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_structs() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "orderId": "o-1",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x"},
+                "tags": ["a"],
+                "voucher": null,
+                "payload": {"a": 1},
+                "score": 1,
+                "day": "2022-01-01",
+                "shippedAt": "2022-01-02T00:00:00Z"
+            })),
+            generate_hypothesis(&json!({
+                "orderId": "o-2",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "tags": [],
+                "voucher": "x",
+                "payload": "a",
+                "score": 1.5,
+                "day": "2022-01-01",
+                "a,b": true
+            })),
+        );
+
+        let actual = render_go(&hypothesis, "order", "shop");
+
+        assert_eq!(
+            actual,
+            r#"package shop
+
+import (
+	"encoding/json"
+	"time"
+)
+
+type Order struct {
+	// JSON property "a,b" cannot be named in a struct tag
+	AB        bool            `json:"-"`
+	CreatedAt time.Time       `json:"createdAt"`
+	Customer  *Customer       `json:"customer,omitempty"`
+	// ISO 8601 date, e.g. `2022-01-31`
+	Day       string          `json:"day"`
+	OrderID   string          `json:"orderId"`
+	Payload   json.RawMessage `json:"payload"`
+	Score     float64         `json:"score"`
+	ShippedAt *time.Time      `json:"shippedAt,omitempty"`
+	Tags      []string        `json:"tags"`
+	Voucher   *string         `json:"voucher"`
+}
+
+type Customer struct {
+	Name string `json:"name"`
+}
+
+type Payload struct {
+	A int64 `json:"a"`
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_root_type() {
+        let hypothesis = generate_hypothesis(&json!([{"id": 1}, null]));
+
+        let actual = render_go(&hypothesis, "Root", "model");
+
+        assert_eq!(
+            actual,
+            r#"package model
+
+type Root []*RootItem
+
+type RootItem struct {
+	ID int64 `json:"id"`
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_go_name() {
+        assert_eq!(go_name("userId"), "UserID");
+        assert_eq!(go_name("api_url"), "APIURL");
+        assert_eq!(go_name("identity"), "Identity");
+        assert_eq!(go_name("HTTPStatus"), "HTTPStatus");
+        assert_eq!(go_name("日本"), "X日本");
+    }
+}
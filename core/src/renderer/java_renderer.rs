@@ -0,0 +1,317 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::model::{NodeType, ObjectNode};
+use crate::renderer::naming::{camel_case, TypeNames};
+use crate::SchemaHypothesis;
+
+const KEYWORDS: &[&str] = &[
+    "_",
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// The `java.lang`, `java.util`, `java.time` and Jackson types the records refer to by their
+/// simple names, which a nested record of the same name would shadow, e.g. `Long` for every
+/// integer property.
+const RESERVED: &[&str] = &[
+    "Boolean",
+    "Double",
+    "JsonNode",
+    "JsonProperty",
+    "List",
+    "LocalDate",
+    "Long",
+    "Object",
+    "OffsetDateTime",
+    "Record",
+    "String",
+];
+
+/// Renders Java source declaring a record (for Jackson with its Java time module) for the root
+/// type (named `root_name`) with a nested record for every nested object, named after the
+/// property it is found at. A root that is not an object is a class only holding the records.
+///
+/// Properties of varying types are a `JsonNode`.
+#[must_use]
+pub fn render_java(schema: &SchemaHypothesis, root_name: &str, package: Option<&str>) -> String {
+    let type_names = TypeNames::reserving(&schema.root, root_name, RESERVED);
+    let mut imports = BTreeSet::new();
+
+    let mut objects = type_names.objects();
+    let mut source = if schema.root.is_object() {
+        match objects.next() {
+            Some((name, object)) => render_record(name, object, "", &type_names, &mut imports),
+            None => String::new(),
+        }
+    } else {
+        let root_name = type_names.root_name();
+        let root_type = type_of(&schema.root, &type_names, &mut imports);
+        let mut holder =
+            format!("/**\n * The records of documents that are a {{@code {root_type}}}.\n */\n");
+        let _ = writeln!(holder, "public final class {root_name} {{");
+        let _ = writeln!(holder, "    private {root_name}() {{\n    }}");
+        holder
+    };
+    for (name, object) in objects {
+        source.push('\n');
+        source.push_str(&render_record(
+            name,
+            object,
+            "    ",
+            &type_names,
+            &mut imports,
+        ));
+    }
+    source.push_str("}\n");
+
+    let mut header = String::new();
+    if let Some(package) = package {
+        let _ = writeln!(header, "package {package};\n");
+    }
+    if !imports.is_empty() {
+        for import in &imports {
+            let _ = writeln!(header, "import {import};");
+        }
+        header.push('\n');
+    }
+    header + &source
+}
+
+/// Renders a record; the top-level record (without `indent`) is left open, so the nested records
+/// can follow.
+fn render_record(
+    name: &str,
+    object: &ObjectNode,
+    indent: &str,
+    type_names: &TypeNames,
+    imports: &mut BTreeSet<&'static str>,
+) -> String {
+    let mut components = Vec::new();
+    let mut fields = BTreeSet::new();
+    for (key, property) in &object.properties {
+        let mut field = camel_case(key);
+        if KEYWORDS.contains(&field.as_str()) {
+            field.push('_');
+        }
+        let base = field.clone();
+        let mut suffix = 2;
+        while fields.contains(&field) {
+            field = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        fields.insert(field.clone());
+
+        let annotation = if field == *key {
+            String::new()
+        } else {
+            imports.insert("com.fasterxml.jackson.annotation.JsonProperty");
+            format!("@JsonProperty({}) ", serde_json::Value::from(key.as_str()))
+        };
+        let field_type = type_of(&property.node_type, type_names, imports);
+        components.push(format!("{indent}        {annotation}{field_type} {field}"));
+    }
+
+    let mut record = format!("{indent}public record {name}(");
+    if !components.is_empty() {
+        let _ = write!(record, "\n{}", components.join(",\n"));
+    }
+    let _ = writeln!(record, ") {{");
+    if !indent.is_empty() {
+        let _ = writeln!(record, "{indent}}}");
+    }
+    record
+}
+
+fn type_of(
+    node_type: &NodeType,
+    type_names: &TypeNames,
+    imports: &mut BTreeSet<&'static str>,
+) -> String {
+    match node_type {
+        NodeType::String(_) => "String".to_string(),
+        NodeType::Date(_) => {
+            imports.insert("java.time.LocalDate");
+            "LocalDate".to_string()
+        }
+        NodeType::DateTime(_) => {
+            imports.insert("java.time.OffsetDateTime");
+            "OffsetDateTime".to_string()
+        }
+        NodeType::Integer(_) => "Long".to_string(),
+        NodeType::Number(_) => "Double".to_string(),
+        NodeType::Boolean => "Boolean".to_string(),
+        NodeType::Array(array) => {
+            imports.insert("java.util.List");
+            match &array.items {
+                Some(items) => format!("List<{}>", type_of(items, type_names, imports)),
+                None => {
+                    imports.insert("com.fasterxml.jackson.databind.JsonNode");
+                    "List<JsonNode>".to_string()
+                }
+            }
+        }
+        NodeType::Object(object) => type_names.name(object).to_string(),
+        NodeType::Any(any) => {
+            let types: Vec<&NodeType> = any
+                .nodes
+                .iter()
+                .filter(|node_type| **node_type != NodeType::Null)
+                .collect();
+            match types.as_slice() {
+                [single] => type_of(single, type_names, imports),
+                [NodeType::Integer(_), NodeType::Number(_)] => "Double".to_string(),
+                _ => {
+                    imports.insert("com.fasterxml.jackson.databind.JsonNode");
+                    "JsonNode".to_string()
+                }
+            }
+        }
+        NodeType::Null => {
+            imports.insert("com.fasterxml.jackson.databind.JsonNode");
+            "JsonNode".to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::java_renderer::render_java;
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_records() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "orderId": "o-1",
+                "created-at": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x", "class": "a"},
+                "items": [{"day": "2022-01-01"}],
+                "id": 1,
+                "note": null
+            })),
+            generate_hypothesis(&json!({
+                "orderId": "o-2",
+                "created-at": "2022-01-01T00:00:00Z",
+                "items": [],
+                "id": "2",
+                "note": "x",
+                "meta": {}
+            })),
+        );
+
+        let actual = render_java(&hypothesis, "order", Some("com.example"));
+
+        assert_eq!(
+            actual,
+            r#"package com.example;
+
+import com.fasterxml.jackson.annotation.JsonProperty;
+import com.fasterxml.jackson.databind.JsonNode;
+import java.time.LocalDate;
+import java.time.OffsetDateTime;
+import java.util.List;
+
+public record Order(
+        @JsonProperty("created-at") OffsetDateTime createdAt,
+        Customer customer,
+        JsonNode id,
+        List<ItemsItem> items,
+        Meta meta,
+        String note,
+        String orderId) {
+
+    public record Customer(
+            @JsonProperty("class") String class_,
+            String name) {
+    }
+
+    public record ItemsItem(
+            LocalDate day) {
+    }
+
+    public record Meta() {
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_root_type() {
+        let hypothesis = generate_hypothesis(&json!([{"id": 1}, null]));
+
+        let actual = render_java(&hypothesis, "Root", None);
+
+        assert_eq!(
+            actual,
+            r#"import java.util.List;
+
+/**
+ * The records of documents that are a {@code List<RootItem>}.
+ */
+public final class Root {
+    private Root() {
+    }
+
+    public record RootItem(
+            Long id) {
+    }
+}
+"#
+        );
+    }
+}
@@ -6,17 +6,87 @@ use serde_json::json;
 use serde_json::value::Value;
 use serde_json::Map;
 
+/// A JSON Schema draft, which is declared as `$schema` of the rendered schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Draft {
+    Draft4,
+    Draft6,
+    Draft7,
+    Draft2019_09,
+    Draft2020_12,
+}
+
+impl Draft {
+    #[must_use]
+    pub fn uri(self) -> &'static str {
+        match self {
+            Draft::Draft4 => "http://json-schema.org/draft-04/schema#",
+            Draft::Draft6 => "http://json-schema.org/draft-06/schema#",
+            Draft::Draft7 => "http://json-schema.org/draft-07/schema#",
+            Draft::Draft2019_09 => "https://json-schema.org/draft/2019-09/schema",
+            Draft::Draft2020_12 => "https://json-schema.org/draft/2020-12/schema",
+        }
+    }
+}
+
+/// Options for rendering a JSON Schema; the default renders the plain schema without any
+/// metadata.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonSchemaOptions {
+    /// The draft to declare and adhere to; if not set, no `$schema` is declared and the schema is
+    /// valid for every draft from draft-06 on.
+    pub draft: Option<Draft>,
+    /// The `$id` of the schema (`id` in draft-04).
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Reject properties that have not been seen, with `additionalProperties: false` (or
+    /// `unevaluatedProperties: false` from draft 2019-09 on) on every object.
+    pub closed: bool,
+    /// Paths of objects that stay open even if `closed` is set, e.g. `$.metadata` or
+    /// `$.items[*].labels`; alternatives of a type share its path.
+    pub open_paths: BTreeSet<String>,
+}
+
 #[must_use]
 #[allow(clippy::missing_panics_doc)]
 pub fn render_schema(schema: &SchemaHypothesis) -> String {
     serde_json::to_string_pretty(&render_json_schema(schema)).unwrap()
 }
 
-fn render_json_schema(schema: &SchemaHypothesis) -> Value {
-    render_node(&schema.root)
+#[must_use]
+pub fn render_json_schema(schema: &SchemaHypothesis) -> Value {
+    render_json_schema_with_options(schema, &JsonSchemaOptions::default())
+}
+
+#[must_use]
+pub fn render_json_schema_with_options(
+    schema: &SchemaHypothesis,
+    options: &JsonSchemaOptions,
+) -> Value {
+    let mut root = render_node(&schema.root, "$", options);
+    if let Value::Object(map) = &mut root {
+        if let Some(draft) = options.draft {
+            map.insert("$schema".to_string(), Value::from(draft.uri()));
+        }
+        if let Some(id) = &options.id {
+            let keyword = match options.draft {
+                Some(Draft::Draft4) => "id",
+                _ => "$id",
+            };
+            map.insert(keyword.to_string(), Value::from(id.as_str()));
+        }
+        if let Some(title) = &options.title {
+            map.insert("title".to_string(), Value::from(title.as_str()));
+        }
+        if let Some(description) = &options.description {
+            map.insert("description".to_string(), Value::from(description.as_str()));
+        }
+    }
+    root
 }
 
-fn render_node(node_type: &NodeType) -> Value {
+fn render_node(node_type: &NodeType, path: &str, options: &JsonSchemaOptions) -> Value {
     match node_type {
         NodeType::String(_) => json!({"type": "string"}),
         NodeType::DateTime(_) => json!({"type": "string", "format": "date-time"}),
@@ -25,35 +95,52 @@ fn render_node(node_type: &NodeType) -> Value {
         NodeType::Number(_) => json!({"type": "number"}),
         NodeType::Boolean => json!({"type": "boolean"}),
         NodeType::Null => json!({"type": "null"}),
-        NodeType::Array(node_types) => Value::Object(generate_array_map(node_types)),
+        NodeType::Array(node_types) => Value::Object(generate_array_map(node_types, path, options)),
         NodeType::Object(ObjectNode { properties }) => {
-            Value::Object(generate_object_map(properties))
+            Value::Object(generate_object_map(properties, path, options))
+        }
+        NodeType::Any(node_types) => {
+            Value::Object(generate_any_map(&node_types.nodes, path, options))
         }
-        NodeType::Any(node_types) => Value::Object(generate_any_map(&node_types.nodes)),
     }
 }
 
-fn generate_any_map(node_types: &BTreeSet<NodeType>) -> Map<String, Value> {
+fn generate_any_map(
+    node_types: &BTreeSet<NodeType>,
+    path: &str,
+    options: &JsonSchemaOptions,
+) -> Map<String, Value> {
     let mut map = Map::new();
     map.insert(
         "anyOf".to_string(),
-        node_types.iter().map(render_node).collect(),
+        node_types
+            .iter()
+            .map(|node_type| render_node(node_type, path, options))
+            .collect(),
     );
 
     map
 }
 
-fn generate_array_map(node_type: &ArrayNode) -> Map<String, Value> {
+fn generate_array_map(
+    node_type: &ArrayNode,
+    path: &str,
+    options: &JsonSchemaOptions,
+) -> Map<String, Value> {
     let mut map = Map::new();
     map.insert("type".to_string(), Value::String("array".to_string()));
-    node_type
-        .items
-        .as_ref()
-        .map(|node_type| map.insert("items".to_string(), render_node(node_type)));
+    if let Some(node_type) = &node_type.items {
+        let items = render_node(node_type, &format!("{path}[*]"), options);
+        map.insert("items".to_string(), items);
+    }
     map
 }
 
-fn generate_object_map(properties: &BTreeMap<String, ObjectProperty>) -> Map<String, Value> {
+fn generate_object_map(
+    properties: &BTreeMap<String, ObjectProperty>,
+    path: &str,
+    options: &JsonSchemaOptions,
+) -> Map<String, Value> {
     let required_props: Vec<Value> = properties
         .iter()
         .filter_map(|(key, value)| {
@@ -67,14 +154,28 @@ fn generate_object_map(properties: &BTreeMap<String, ObjectProperty>) -> Map<Str
 
     let object_properties: Map<String, Value> = properties
         .iter()
-        .map(|(key, value)| (key.to_string(), render_node(&value.node_type)))
+        .map(|(key, value)| {
+            let property_path = format!("{path}.{key}");
+            let schema = render_node(&value.node_type, &property_path, options);
+            (key.to_string(), schema)
+        })
         .collect();
 
     let mut map = Map::new();
 
     map.insert("type".to_string(), Value::String("object".to_string()));
-    map.insert("required".to_string(), Value::Array(required_props));
+    // draft-04 requires at least one element in `required`
+    if !(required_props.is_empty() && options.draft == Some(Draft::Draft4)) {
+        map.insert("required".to_string(), Value::Array(required_props));
+    }
     map.insert("properties".to_string(), Value::Object(object_properties));
+    if options.closed && !options.open_paths.contains(path) {
+        let keyword = match options.draft {
+            Some(Draft::Draft2019_09 | Draft::Draft2020_12) => "unevaluatedProperties",
+            _ => "additionalProperties",
+        };
+        map.insert(keyword.to_string(), Value::Bool(false));
+    }
 
     map
 }
@@ -84,11 +185,14 @@ mod test {
     use maplit::{btreemap, btreeset};
     use serde_json::json;
 
+    use crate::generate_hypothesis;
     use crate::model::{
         AnyNode, ArrayNode, IntegerNode, NodeType, ObjectNode, ObjectProperty, SchemaHypothesis,
         StringNode,
     };
-    use crate::renderer::json_schema_renderer::{render_json_schema, render_node};
+    use crate::renderer::json_schema_renderer::{
+        render_json_schema, render_json_schema_with_options, render_node, Draft, JsonSchemaOptions,
+    };
 
     #[test]
     fn test_object() {
@@ -176,7 +280,7 @@ mod test {
     fn test_any() {
         let node_type = AnyNode::new(btreeset![StringNode::new().into(), NodeType::Boolean]).into();
 
-        let actual = render_node(&node_type);
+        let actual = render_node(&node_type, "$", &JsonSchemaOptions::default());
 
         assert_eq!(
             actual,
@@ -193,7 +297,7 @@ mod test {
     fn test_any_one() {
         let node_type = AnyNode::new(btreeset![StringNode::new().into()]).into();
 
-        let actual = render_node(&node_type);
+        let actual = render_node(&node_type, "$", &JsonSchemaOptions::default());
 
         assert_eq!(
             actual,
@@ -209,7 +313,7 @@ mod test {
     fn test_any_empty() {
         let node_type = AnyNode::new(btreeset![]).into();
 
-        let actual = render_node(&node_type);
+        let actual = render_node(&node_type, "$", &JsonSchemaOptions::default());
 
         assert_eq!(
             actual,
@@ -227,7 +331,7 @@ mod test {
         .into()])
         .into();
 
-        let actual = render_node(&node_type);
+        let actual = render_node(&node_type, "$", &JsonSchemaOptions::default());
 
         assert_eq!(
             actual,
@@ -246,4 +350,100 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn test_metadata() {
+        let hypothesis = SchemaHypothesis::new(IntegerNode::new());
+        let options = JsonSchemaOptions {
+            draft: Some(Draft::Draft2020_12),
+            id: Some("https://example.com/id.schema.json".to_string()),
+            title: Some("ID".to_string()),
+            description: Some("An identifier".to_string()),
+            ..JsonSchemaOptions::default()
+        };
+
+        let actual = render_json_schema_with_options(&hypothesis, &options);
+
+        assert_eq!(
+            actual,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$id": "https://example.com/id.schema.json",
+                "title": "ID",
+                "description": "An identifier",
+                "type": "integer"
+            })
+        );
+    }
+
+    #[test]
+    fn test_draft_04() {
+        let hypothesis = SchemaHypothesis::new(ObjectNode::new(btreemap! {
+            "name".to_string() => ObjectProperty::new(StringNode::new()).optional(),
+        }));
+        let options = JsonSchemaOptions {
+            draft: Some(Draft::Draft4),
+            id: Some("urn:example:name".to_string()),
+            ..JsonSchemaOptions::default()
+        };
+
+        let actual = render_json_schema_with_options(&hypothesis, &options);
+
+        assert_eq!(
+            actual,
+            json!({
+                "$schema": "http://json-schema.org/draft-04/schema#",
+                "id": "urn:example:name",
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_closed() {
+        let hypothesis = generate_hypothesis(&json!({
+            "id": 1,
+            "labels": {"team": "a"},
+            "items": [{"sku": "x", "extensions": {}}]
+        }));
+        let options = JsonSchemaOptions {
+            closed: true,
+            open_paths: btreeset!["$.labels".to_string(), "$.items[*].extensions".to_string()],
+            ..JsonSchemaOptions::default()
+        };
+
+        let actual = render_json_schema_with_options(&hypothesis, &options);
+
+        assert_eq!(actual["additionalProperties"], json!(false));
+        assert_eq!(
+            actual["properties"]["labels"].get("additionalProperties"),
+            None
+        );
+        let item = &actual["properties"]["items"]["items"];
+        assert_eq!(item["additionalProperties"], json!(false));
+        assert_eq!(
+            item["properties"]["extensions"].get("additionalProperties"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_closed_2020_12() {
+        let hypothesis = generate_hypothesis(&json!({"id": 1}));
+        let options = JsonSchemaOptions {
+            draft: Some(Draft::Draft2020_12),
+            closed: true,
+            ..JsonSchemaOptions::default()
+        };
+
+        let actual = render_json_schema_with_options(&hypothesis, &options);
+
+        assert_eq!(actual["unevaluatedProperties"], json!(false));
+        assert_eq!(actual.get("additionalProperties"), None);
+    }
 }
@@ -0,0 +1,288 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::model::{NodeType, ObjectNode};
+use crate::renderer::naming::{camel_case, TypeNames};
+use crate::SchemaHypothesis;
+
+const KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// The Kotlin and Jackson types the data classes refer to by their simple names, which a class
+/// of the same name in the package would shadow, e.g. `List` for every array property.
+const RESERVED: &[&str] = &[
+    "Any",
+    "Boolean",
+    "Double",
+    "JsonNode",
+    "JsonProperty",
+    "List",
+    "LocalDate",
+    "Long",
+    "Nothing",
+    "OffsetDateTime",
+    "String",
+    "Unit",
+];
+
+/// Renders Kotlin source declaring a data class (for Jackson with its Kotlin and Java time
+/// modules) for the root type (named `root_name`) and for every nested object, named after the
+/// property it is found at.
+///
+/// Optional and nullable properties are nullable, optional ones default to `null`. Properties of
+/// varying types are a `JsonNode`.
+#[must_use]
+pub fn render_kotlin(schema: &SchemaHypothesis, root_name: &str, package: Option<&str>) -> String {
+    let type_names = TypeNames::reserving(&schema.root, root_name, RESERVED);
+    let mut imports = BTreeSet::new();
+
+    let mut declarations = Vec::new();
+    if !schema.root.is_object() {
+        declarations.push(format!(
+            "typealias {} = {}\n",
+            type_names.root_name(),
+            type_of(&schema.root, &type_names, &mut imports)
+        ));
+    }
+    for (name, object) in type_names.objects() {
+        declarations.push(render_class(name, object, &type_names, &mut imports));
+    }
+
+    let mut header = String::new();
+    if let Some(package) = package {
+        let _ = writeln!(header, "package {package}");
+    }
+    if !imports.is_empty() {
+        if !header.is_empty() {
+            header.push('\n');
+        }
+        for import in &imports {
+            let _ = writeln!(header, "import {import}");
+        }
+    }
+    if !header.is_empty() {
+        declarations.insert(0, header);
+    }
+    declarations.join("\n")
+}
+
+fn render_class(
+    name: &str,
+    object: &ObjectNode,
+    type_names: &TypeNames,
+    imports: &mut BTreeSet<&'static str>,
+) -> String {
+    if object.properties.is_empty() {
+        // data classes need at least one property
+        return format!("class {name}\n");
+    }
+
+    let mut class = format!("data class {name}(\n");
+    let mut fields = BTreeSet::new();
+    for (key, property) in &object.properties {
+        let mut field = camel_case(key);
+        let mut suffix = 2;
+        while fields.contains(&field) {
+            field = format!("{}{suffix}", camel_case(key));
+            suffix += 1;
+        }
+        fields.insert(field.clone());
+
+        let annotation = if field == *key {
+            String::new()
+        } else {
+            imports.insert("com.fasterxml.jackson.annotation.JsonProperty");
+            format!("@JsonProperty({}) ", string_literal(key))
+        };
+        let field = if KEYWORDS.contains(&field.as_str()) {
+            format!("`{field}`")
+        } else {
+            field
+        };
+        let mut field_type = type_of(&property.node_type, type_names, imports);
+        let mut default = "";
+        if !property.required {
+            if !field_type.ends_with('?') {
+                field_type.push('?');
+            }
+            default = " = null";
+        }
+        let _ = writeln!(class, "    {annotation}val {field}: {field_type}{default},");
+    }
+    class.push_str(")\n");
+    class
+}
+
+fn type_of(
+    node_type: &NodeType,
+    type_names: &TypeNames,
+    imports: &mut BTreeSet<&'static str>,
+) -> String {
+    match node_type {
+        NodeType::String(_) => "String".to_string(),
+        NodeType::Date(_) => {
+            imports.insert("java.time.LocalDate");
+            "LocalDate".to_string()
+        }
+        NodeType::DateTime(_) => {
+            imports.insert("java.time.OffsetDateTime");
+            "OffsetDateTime".to_string()
+        }
+        NodeType::Integer(_) => "Long".to_string(),
+        NodeType::Number(_) => "Double".to_string(),
+        NodeType::Boolean => "Boolean".to_string(),
+        NodeType::Null => {
+            imports.insert("com.fasterxml.jackson.databind.JsonNode");
+            "JsonNode?".to_string()
+        }
+        NodeType::Array(array) => match &array.items {
+            Some(items) => format!("List<{}>", type_of(items, type_names, imports)),
+            None => {
+                imports.insert("com.fasterxml.jackson.databind.JsonNode");
+                "List<JsonNode>".to_string()
+            }
+        },
+        NodeType::Object(object) => type_names.name(object).to_string(),
+        NodeType::Any(any) => {
+            let types: Vec<&NodeType> = any
+                .nodes
+                .iter()
+                .filter(|node_type| **node_type != NodeType::Null)
+                .collect();
+            let any_type = match types.as_slice() {
+                [single] => type_of(single, type_names, imports),
+                [NodeType::Integer(_), NodeType::Number(_)] => "Double".to_string(),
+                _ => {
+                    imports.insert("com.fasterxml.jackson.databind.JsonNode");
+                    "JsonNode".to_string()
+                }
+            };
+            if any.nodes.contains(&NodeType::Null) {
+                format!("{any_type}?")
+            } else {
+                any_type
+            }
+        }
+    }
+}
+
+/// A Kotlin string literal of `text`; other than in JSON, `$` starts a template.
+fn string_literal(text: &str) -> String {
+    serde_json::Value::from(text)
+        .to_string()
+        .replace('$', "\\$")
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::kotlin_renderer::render_kotlin;
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_data_classes() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "orderId": "o-1",
+                "created-at": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x", "class": "a"},
+                "items": [{"day": "2022-01-01"}],
+                "id": 1,
+                "note": null,
+                "score": 1
+            })),
+            generate_hypothesis(&json!({
+                "orderId": "o-2",
+                "created-at": "2022-01-01T00:00:00Z",
+                "items": [],
+                "id": "2",
+                "note": "x",
+                "score": 1.5,
+                "meta": {},
+                "$ref": "r"
+            })),
+        );
+
+        let actual = render_kotlin(&hypothesis, "order", Some("com.example"));
+
+        assert_eq!(
+            actual,
+            r#"package com.example
+
+import com.fasterxml.jackson.annotation.JsonProperty
+import com.fasterxml.jackson.databind.JsonNode
+import java.time.LocalDate
+import java.time.OffsetDateTime
+
+data class Order(
+    @JsonProperty("\$ref") val ref: String? = null,
+    @JsonProperty("created-at") val createdAt: OffsetDateTime,
+    val customer: Customer? = null,
+    val id: JsonNode,
+    val items: List<ItemsItem>,
+    val meta: Meta? = null,
+    val note: String?,
+    val orderId: String,
+    val score: Double,
+)
+
+data class Customer(
+    val `class`: String,
+    val name: String,
+)
+
+data class ItemsItem(
+    val day: LocalDate,
+)
+
+class Meta
+"#
+        );
+    }
+
+    #[test]
+    fn test_root_type() {
+        let hypothesis = generate_hypothesis(&json!([{"id": 1}, null]));
+
+        let actual = render_kotlin(&hypothesis, "Root", None);
+
+        assert_eq!(
+            actual,
+            r#"typealias Root = List<RootItem?>
+
+data class RootItem(
+    val id: Long,
+)
+"#
+        );
+    }
+}
@@ -1,3 +1,28 @@
+mod avro_renderer;
+mod bigquery_renderer;
+mod go_renderer;
+mod java_renderer;
 mod json_schema_renderer;
+mod kotlin_renderer;
+mod naming;
+mod openapi_renderer;
+mod protobuf_renderer;
+mod pydantic_renderer;
+mod rust_renderer;
+mod sql_renderer;
+mod typescript_renderer;
 
-pub use json_schema_renderer::render_schema;
+pub use avro_renderer::{render_avro, AvroOptions, AvroSchema};
+pub use bigquery_renderer::{render_bigquery, BigQueryFallback, BigQueryOptions, BigQuerySchema};
+pub use go_renderer::render_go;
+pub use java_renderer::render_java;
+pub use json_schema_renderer::{
+    render_json_schema, render_json_schema_with_options, render_schema, Draft, JsonSchemaOptions,
+};
+pub use kotlin_renderer::render_kotlin;
+pub use openapi_renderer::{render_openapi, OpenApiOptions, OpenApiVersion};
+pub use protobuf_renderer::{render_protobuf, ProtobufOptions};
+pub use pydantic_renderer::render_pydantic;
+pub use rust_renderer::render_rust;
+pub use sql_renderer::{render_sql, SqlDialect, SqlOptions};
+pub use typescript_renderer::render_typescript;
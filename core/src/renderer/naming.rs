@@ -0,0 +1,358 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::model::{NodeType, ObjectNode};
+
+/// Names of the object types of a hypothesis, for renderers of languages that declare object
+/// types by name instead of inline.
+///
+/// An object is named after the property it is found at (with an `Item` suffix for the items of
+/// arrays), prefixed with the name of its parent if that name is already taken. Every object has
+/// a name of its own, even if another object at a different path has the same structure.
+pub struct TypeNames<'a> {
+    root_name: String,
+    /// The names by the address of the objects, as objects of the same structure are equal.
+    names: BTreeMap<*const ObjectNode, String>,
+    objects: Vec<(String, &'a ObjectNode)>,
+    taken: BTreeSet<String>,
+    /// Whether names only consist of ASCII characters.
+    ascii: bool,
+}
+
+impl<'a> TypeNames<'a> {
+    pub fn new(root: &'a NodeType, root_name: &str) -> Self {
+        Self::reserving(root, root_name, &[])
+    }
+
+    /// Like [`TypeNames::new`], but never names a type with one of the `reserved` names, e.g.
+    /// the names of built-in types of the target language. This includes the root type, which gets
+    /// a suffix then, e.g. `String2`.
+    pub fn reserving(root: &'a NodeType, root_name: &str, reserved: &[&str]) -> Self {
+        Self::build(root, root_name, reserved, false)
+    }
+
+    /// Like [`TypeNames::new`], but for languages whose identifiers consist of ASCII characters
+    /// only; see [`ascii`], e.g. `größe` is named `Groesse` and `日本` is named `U65e5U672c`.
+    pub fn ascii(root: &'a NodeType, root_name: &str) -> Self {
+        Self::build(root, root_name, &[], true)
+    }
+
+    fn build(root: &'a NodeType, root_name: &str, reserved: &[&str], ascii: bool) -> Self {
+        let mut type_names = TypeNames {
+            root_name: String::new(),
+            names: BTreeMap::new(),
+            objects: Vec::new(),
+            taken: reserved.iter().map(|name| (*name).to_string()).collect(),
+            ascii,
+        };
+        let root_name = type_names.type_name(root_name);
+        type_names.root_name = match root {
+            NodeType::Object(object) => type_names.add(object, "", &root_name),
+            // the root type is declared under this name, even if it is not an object
+            _ => type_names.take("", &root_name),
+        };
+        let root_name = type_names.root_name.clone();
+        type_names.collect(root, "", &root_name);
+        type_names
+    }
+
+    /// The name of the root type, which may differ from the requested one if that is reserved.
+    pub fn root_name(&self) -> &str {
+        &self.root_name
+    }
+
+    /// The name of `object`, which has to be part of the hypothesis the names were collected from
+    /// (the very node, not a copy of it).
+    pub fn name(&self, object: &ObjectNode) -> &str {
+        self.names
+            .get(&(object as *const ObjectNode))
+            .map_or("Object", String::as_str)
+    }
+
+    /// All named objects, the root (if it is an object) first, then in order of appearance.
+    pub fn objects(&self) -> impl Iterator<Item = (&str, &'a ObjectNode)> + '_ {
+        self.objects
+            .iter()
+            .map(|(name, object)| (name.as_str(), *object))
+    }
+
+    fn collect(&mut self, node_type: &'a NodeType, parent: &str, name: &str) {
+        match node_type {
+            NodeType::Object(object) => {
+                let name = match self.names.get(&(object as *const ObjectNode)) {
+                    Some(name) => name.clone(),
+                    None => self.add(object, parent, name),
+                };
+                for (key, property) in &object.properties {
+                    let key = self.type_name(key);
+                    self.collect(&property.node_type, &name, &key);
+                }
+            }
+            NodeType::Array(array) => {
+                if let Some(items) = &array.items {
+                    self.collect(items, parent, &format!("{name}Item"));
+                }
+            }
+            NodeType::Any(any) => {
+                for node_type in &any.nodes {
+                    self.collect(node_type, parent, name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn type_name(&self, text: &str) -> String {
+        if self.ascii {
+            pascal_case(&ascii(text))
+        } else {
+            pascal_case(text)
+        }
+    }
+
+    fn add(&mut self, object: &'a ObjectNode, parent: &str, name: &str) -> String {
+        let name = self.take(parent, name);
+        self.names.insert(object, name.clone());
+        self.objects.push((name.clone(), object));
+        name
+    }
+
+    /// Takes `name`, or `name` prefixed with `parent` (and a number, if necessary) if it is taken.
+    fn take(&mut self, parent: &str, name: &str) -> String {
+        let prefixed = format!("{parent}{name}");
+        let mut name = name.to_string();
+        if self.taken.contains(&name) {
+            name = prefixed.clone();
+        }
+        let mut suffix = 2;
+        while self.taken.contains(&name) {
+            name = format!("{prefixed}{suffix}");
+            suffix += 1;
+        }
+        self.taken.insert(name.clone());
+        name
+    }
+}
+
+/// Splits `text` into words at every character that is not alphanumeric.
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+/// Replaces the characters of `text` that are not ASCII: common Latin letters by their usual
+/// transliteration, e.g. `größe` by `groesse`, others by their code point as a word of its own,
+/// e.g. `日本` by ` u65e5 u672c `, so that different texts stay different.
+pub fn ascii(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            result.push(c);
+        } else if let Some(transliteration) = transliterate(c) {
+            result.push_str(transliteration);
+        } else {
+            let _ = write!(result, " u{:x} ", u32::from(c));
+        }
+    }
+    result
+}
+
+fn transliterate(c: char) -> Option<&'static str> {
+    let transliteration = match c {
+        'ä' | 'æ' => "ae",
+        'Ä' | 'Æ' => "Ae",
+        'ö' | 'ø' | 'œ' => "oe",
+        'Ö' | 'Ø' | 'Œ' => "Oe",
+        'ü' => "ue",
+        'Ü' => "Ue",
+        'ß' => "ss",
+        'à' | 'á' | 'â' | 'ã' | 'å' => "a",
+        'À' | 'Á' | 'Â' | 'Ã' | 'Å' => "A",
+        'ç' => "c",
+        'Ç' => "C",
+        'è' | 'é' | 'ê' | 'ë' => "e",
+        'È' | 'É' | 'Ê' | 'Ë' => "E",
+        'ì' | 'í' | 'î' | 'ï' => "i",
+        'Ì' | 'Í' | 'Î' | 'Ï' => "I",
+        'ñ' => "n",
+        'Ñ' => "N",
+        'ò' | 'ó' | 'ô' | 'õ' => "o",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' => "O",
+        'ù' | 'ú' | 'û' => "u",
+        'Ù' | 'Ú' | 'Û' => "U",
+        'ý' | 'ÿ' => "y",
+        'Ý' => "Y",
+        _ => return None,
+    };
+    Some(transliteration)
+}
+
+/// Turns `text` into a type name, e.g. `order_items` or `order-items` into `OrderItems`; words in
+/// camel case are kept, e.g. `orderItems` becomes `OrderItems`.
+pub fn pascal_case(text: &str) -> String {
+    let name: String = words(text)
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+    match name.chars().next() {
+        None => "Value".to_string(),
+        Some(first) if first.is_numeric() => format!("T{name}"),
+        Some(_) => name,
+    }
+}
+
+/// Turns `text` into a field name, e.g. `orderItems` or `order-items` into `order_items`; runs of
+/// capitals are kept together, e.g. `HTTPStatus` becomes `http_status`.
+pub fn snake_case(text: &str) -> String {
+    let mut name = String::new();
+    for word in words(text) {
+        if !name.is_empty() {
+            name.push('_');
+        }
+        let chars: Vec<char> = word.chars().collect();
+        for (i, c) in chars.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let starts_word = c.is_uppercase()
+                && previous.is_some_and(|previous| {
+                    !previous.is_uppercase() || next.is_some_and(|next| next.is_lowercase())
+                });
+            if starts_word {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        }
+    }
+    match name.chars().next() {
+        None => "value".to_string(),
+        Some(first) if first.is_numeric() => format!("field_{name}"),
+        Some(_) => name,
+    }
+}
+
+/// Turns `text` into a field name in camel case, e.g. `order_items` or `OrderItems` into
+/// `orderItems`; runs of capitals are kept together, e.g. `HTTPStatus` becomes `httpStatus`.
+pub fn camel_case(text: &str) -> String {
+    let mut words = snake_case(text)
+        .split('_')
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    for word in words.iter_mut().skip(1) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            *word = first.to_uppercase().chain(chars).collect();
+        }
+    }
+    words.concat()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::generate_hypothesis;
+    use crate::renderer::naming::{ascii, camel_case, pascal_case, snake_case, TypeNames};
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(pascal_case("order"), "Order");
+        assert_eq!(pascal_case("order_items"), "OrderItems");
+        assert_eq!(pascal_case("order-items"), "OrderItems");
+        assert_eq!(pascal_case("orderItems"), "OrderItems");
+        assert_eq!(pascal_case("@type"), "Type");
+        assert_eq!(pascal_case("1st"), "T1st");
+        assert_eq!(pascal_case("-"), "Value");
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("order"), "order");
+        assert_eq!(snake_case("orderItems"), "order_items");
+        assert_eq!(snake_case("order-items"), "order_items");
+        assert_eq!(snake_case("OrderItems"), "order_items");
+        assert_eq!(snake_case("HTTPStatus"), "http_status");
+        assert_eq!(snake_case("vatID"), "vat_id");
+        assert_eq!(snake_case("item2Id"), "item2_id");
+        assert_eq!(snake_case("@type"), "type");
+        assert_eq!(snake_case("1st"), "field_1st");
+        assert_eq!(snake_case("-"), "value");
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("order"), "order");
+        assert_eq!(camel_case("order_items"), "orderItems");
+        assert_eq!(camel_case("OrderItems"), "orderItems");
+        assert_eq!(camel_case("HTTPStatus"), "httpStatus");
+        assert_eq!(camel_case("1st"), "field1st");
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert_eq!(ascii("größe"), "groesse");
+        assert_eq!(ascii("Ärger"), "Aerger");
+        assert_eq!(ascii("café"), "cafe");
+        assert_eq!(ascii("日本"), " u65e5  u672c ");
+        assert_eq!(snake_case(&ascii("日本")), "u65e5_u672c");
+    }
+
+    #[test]
+    fn test_ascii_names() {
+        let hypothesis = generate_hypothesis(&json!({"größe": {"a": 1}, "日本": {"b": 1}}));
+
+        let type_names = TypeNames::ascii(&hypothesis.root, "bestellung");
+
+        assert_eq!(type_names.root_name(), "Bestellung");
+        let names: Vec<&str> = type_names.objects().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["Bestellung", "Groesse", "U65e5U672c"]);
+    }
+
+    #[test]
+    fn test_reserved_root_name() {
+        let object = generate_hypothesis(&json!({"string": {"a": 1}}));
+        let list = generate_hypothesis(&json!([{"a": 1}]));
+
+        let object_names = TypeNames::reserving(&object.root, "string", &["String"]);
+        let list_names = TypeNames::reserving(&list.root, "string", &["String"]);
+
+        assert_eq!(object_names.root_name(), "String2");
+        let names: Vec<&str> = object_names.objects().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["String2", "String2String"]);
+        assert_eq!(list_names.root_name(), "String2");
+        let names: Vec<&str> = list_names.objects().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["String2Item"]);
+    }
+
+    #[test]
+    fn test_names() {
+        let hypothesis = generate_hypothesis(&json!({
+            "customer": {"address": {"city": "x"}, "name": "n"},
+            "items": [{"sku": "a", "price": {"amount": 1}}],
+            "seller": {"address": {"street": "y"}},
+            "billing": {"address": {"city": "x"}}
+        }));
+
+        let type_names = TypeNames::new(&hypothesis.root, "order");
+
+        let names: Vec<&str> = type_names.objects().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Order",
+                "Billing",
+                "Address",
+                "Customer",
+                "CustomerAddress",
+                "ItemsItem",
+                "Price",
+                "Seller",
+                "SellerAddress"
+            ]
+        );
+    }
+}
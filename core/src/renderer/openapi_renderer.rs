@@ -0,0 +1,357 @@
+use std::collections::BTreeSet;
+
+use serde_json::{json, Map, Value};
+
+use crate::model::{ArrayNode, NodeType, ObjectNode};
+use crate::renderer::naming::TypeNames;
+use crate::SchemaHypothesis;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpenApiVersion {
+    /// OpenAPI 3.0, which has no `null` type but marks schemas as `nullable` instead.
+    V3_0,
+    /// OpenAPI 3.1, whose schemas are JSON Schema 2020-12.
+    V3_1,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenApiOptions {
+    pub version: OpenApiVersion,
+    /// The name of the component of the root type; nested objects are named after their
+    /// properties.
+    pub root_name: String,
+    /// The title of the API, defaults to the root name.
+    pub title: Option<String>,
+}
+
+impl Default for OpenApiOptions {
+    fn default() -> Self {
+        OpenApiOptions {
+            version: OpenApiVersion::V3_0,
+            root_name: "Root".to_string(),
+            title: None,
+        }
+    }
+}
+
+/// Renders an OpenAPI document without any paths, which declares the root type and every nested
+/// object as a separate component in `components.schemas`.
+#[must_use]
+pub fn render_openapi(schema: &SchemaHypothesis, options: &OpenApiOptions) -> Value {
+    // component names have to match `^[a-zA-Z0-9\.\-_]+$`
+    let type_names = TypeNames::ascii(&schema.root, &options.root_name);
+    let renderer = OpenApiRenderer {
+        type_names: &type_names,
+        version: options.version,
+    };
+
+    let mut schemas = Map::new();
+    if !schema.root.is_object() {
+        schemas.insert(
+            type_names.root_name().to_string(),
+            renderer.render_node(&schema.root),
+        );
+    }
+    for (name, object) in type_names.objects() {
+        schemas.insert(name.to_string(), renderer.render_object(object));
+    }
+
+    let openapi = match options.version {
+        OpenApiVersion::V3_0 => "3.0.3",
+        OpenApiVersion::V3_1 => "3.1.0",
+    };
+    json!({
+        "openapi": openapi,
+        "info": {
+            "title": options.title.as_deref().unwrap_or(&options.root_name),
+            "version": "1.0.0"
+        },
+        "paths": {},
+        "components": {
+            "schemas": schemas
+        }
+    })
+}
+
+struct OpenApiRenderer<'a> {
+    type_names: &'a TypeNames<'a>,
+    version: OpenApiVersion,
+}
+
+impl OpenApiRenderer<'_> {
+    fn render_node(&self, node_type: &NodeType) -> Value {
+        match node_type {
+            NodeType::String(_) => json!({"type": "string"}),
+            NodeType::DateTime(_) => json!({"type": "string", "format": "date-time"}),
+            NodeType::Date(_) => json!({"type": "string", "format": "date"}),
+            NodeType::Integer(_) => json!({"type": "integer", "format": "int64"}),
+            NodeType::Number(_) => json!({"type": "number", "format": "double"}),
+            NodeType::Boolean => json!({"type": "boolean"}),
+            NodeType::Null => match self.version {
+                // without a `null` type, `nullable` only takes effect next to a `type`, and the
+                // enum excludes every value of that type
+                OpenApiVersion::V3_0 => json!({"type": "string", "nullable": true, "enum": [null]}),
+                OpenApiVersion::V3_1 => json!({"type": "null"}),
+            },
+            NodeType::Array(array) => self.render_array(array),
+            NodeType::Object(object) => {
+                json!({"$ref": format!("#/components/schemas/{}", self.type_names.name(object))})
+            }
+            NodeType::Any(any) => self.render_any(&any.nodes),
+        }
+    }
+
+    fn render_array(&self, array: &ArrayNode) -> Value {
+        // OpenAPI requires `items` for arrays
+        let items = array
+            .items
+            .as_ref()
+            .map_or_else(|| json!({}), |items| self.render_node(items));
+        json!({"type": "array", "items": items})
+    }
+
+    fn render_object(&self, object: &ObjectNode) -> Value {
+        let properties: Map<String, Value> = object
+            .properties
+            .iter()
+            .map(|(key, property)| (key.clone(), self.render_node(&property.node_type)))
+            .collect();
+        let required: Vec<&String> = object
+            .properties
+            .iter()
+            .filter(|(_, property)| property.required)
+            .map(|(key, _)| key)
+            .collect();
+
+        let mut map = Map::new();
+        map.insert("type".to_string(), json!("object"));
+        map.insert("properties".to_string(), Value::Object(properties));
+        // OpenAPI 3.0 requires at least one element in `required`
+        if !required.is_empty() {
+            map.insert("required".to_string(), json!(required));
+        }
+        Value::Object(map)
+    }
+
+    /// Null alternatives are translated to `nullable` in OpenAPI 3.0 and to type arrays (or a
+    /// `null` alternative) in OpenAPI 3.1.
+    fn render_any(&self, nodes: &BTreeSet<NodeType>) -> Value {
+        let nullable = nodes.contains(&NodeType::Null);
+        let others: Vec<Value> = nodes
+            .iter()
+            .filter(|node_type| **node_type != NodeType::Null)
+            .map(|node_type| self.render_node(node_type))
+            .collect();
+
+        match (others.as_slice(), nullable) {
+            ([], _) => self.render_node(&NodeType::Null),
+            ([single], false) => single.clone(),
+            ([single], true) => self.nullable(single.clone()),
+            (_, false) => json!({"anyOf": others}),
+            (_, true) => match self.version {
+                // `nullable` has no effect next to `anyOf` without a `type`, so every alternative
+                // accepts null instead
+                OpenApiVersion::V3_0 => {
+                    let others: Vec<Value> = others
+                        .into_iter()
+                        .map(|other| self.nullable(other))
+                        .collect();
+                    json!({"anyOf": others})
+                }
+                OpenApiVersion::V3_1 => {
+                    let mut others = others;
+                    others.push(json!({"type": "null"}));
+                    json!({"anyOf": others})
+                }
+            },
+        }
+    }
+
+    fn nullable(&self, schema: Value) -> Value {
+        let mut map = match schema {
+            Value::Object(map) if !map.contains_key("$ref") => map,
+            // properties next to `$ref` are ignored, and `nullable` needs a `type` in OpenAPI 3.0;
+            // references are to objects only
+            schema => {
+                return match self.version {
+                    OpenApiVersion::V3_0 => {
+                        json!({"type": "object", "allOf": [schema], "nullable": true})
+                    }
+                    OpenApiVersion::V3_1 => json!({"anyOf": [schema, {"type": "null"}]}),
+                }
+            }
+        };
+        match self.version {
+            OpenApiVersion::V3_0 => {
+                map.insert("nullable".to_string(), Value::Bool(true));
+            }
+            OpenApiVersion::V3_1 => {
+                let schema_type = map.remove("type").unwrap_or(Value::Null);
+                map.insert("type".to_string(), json!([schema_type, "null"]));
+            }
+        }
+        Value::Object(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::generate_hypothesis;
+    use crate::renderer::openapi_renderer::{render_openapi, OpenApiOptions, OpenApiVersion};
+
+    #[test]
+    fn test_components() {
+        let hypothesis = generate_hypothesis(&json!({
+            "id": 1,
+            "created": "2022-01-01T00:00:00Z",
+            "customer": {"name": "x", "birthday": "2000-01-01"},
+            "items": [{"sku": "a"}]
+        }));
+        let options = OpenApiOptions {
+            root_name: "Order".to_string(),
+            ..OpenApiOptions::default()
+        };
+
+        let actual = render_openapi(&hypothesis, &options);
+
+        assert_eq!(
+            actual,
+            json!({
+                "openapi": "3.0.3",
+                "info": {"title": "Order", "version": "1.0.0"},
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Order": {
+                            "type": "object",
+                            "properties": {
+                                "id": {"type": "integer", "format": "int64"},
+                                "created": {"type": "string", "format": "date-time"},
+                                "customer": {"$ref": "#/components/schemas/Customer"},
+                                "items": {
+                                    "type": "array",
+                                    "items": {"$ref": "#/components/schemas/ItemsItem"}
+                                }
+                            },
+                            "required": ["created", "customer", "id", "items"]
+                        },
+                        "Customer": {
+                            "type": "object",
+                            "properties": {
+                                "birthday": {"type": "string", "format": "date"},
+                                "name": {"type": "string"}
+                            },
+                            "required": ["birthday", "name"]
+                        },
+                        "ItemsItem": {
+                            "type": "object",
+                            "properties": {
+                                "sku": {"type": "string"}
+                            },
+                            "required": ["sku"]
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    fn root_property(
+        version: OpenApiVersion,
+        documents: &[serde_json::Value],
+    ) -> serde_json::Value {
+        let hypothesis = documents
+            .iter()
+            .map(generate_hypothesis)
+            .reduce(crate::merge_hypothesis)
+            .unwrap();
+        let options = OpenApiOptions {
+            version,
+            ..OpenApiOptions::default()
+        };
+        render_openapi(&hypothesis, &options)["components"]["schemas"]["Root"]["properties"]["a"]
+            .clone()
+    }
+
+    #[test]
+    fn test_nullable_3_0() {
+        let documents = [json!({"a": 1}), json!({"a": null})];
+        assert_eq!(
+            root_property(OpenApiVersion::V3_0, &documents),
+            json!({"type": "integer", "format": "int64", "nullable": true})
+        );
+
+        let documents = [json!({"a": {"b": 1}}), json!({"a": null})];
+        assert_eq!(
+            root_property(OpenApiVersion::V3_0, &documents),
+            json!({
+                "type": "object",
+                "allOf": [{"$ref": "#/components/schemas/A"}],
+                "nullable": true
+            })
+        );
+
+        let documents = [json!({"a": 1}), json!({"a": "x"}), json!({"a": null})];
+        assert_eq!(
+            root_property(OpenApiVersion::V3_0, &documents),
+            json!({
+                "anyOf": [
+                    {"type": "integer", "format": "int64", "nullable": true},
+                    {"type": "string", "nullable": true}
+                ]
+            })
+        );
+
+        let documents = [json!({"a": null})];
+        assert_eq!(
+            root_property(OpenApiVersion::V3_0, &documents),
+            json!({"type": "string", "nullable": true, "enum": [null]})
+        );
+    }
+
+    #[test]
+    fn test_nullable_3_1() {
+        let documents = [json!({"a": "x"}), json!({"a": null})];
+        assert_eq!(
+            root_property(OpenApiVersion::V3_1, &documents),
+            json!({"type": ["string", "null"]})
+        );
+
+        let documents = [json!({"a": {"b": 1}}), json!({"a": null})];
+        assert_eq!(
+            root_property(OpenApiVersion::V3_1, &documents),
+            json!({"anyOf": [{"$ref": "#/components/schemas/A"}, {"type": "null"}]})
+        );
+    }
+
+    #[test]
+    fn test_ascii_names() {
+        let hypothesis = generate_hypothesis(&json!({"größe": {"wert": 1}}));
+
+        let actual = render_openapi(&hypothesis, &OpenApiOptions::default());
+
+        assert_eq!(
+            actual["components"]["schemas"]["Root"]["properties"]["größe"],
+            json!({"$ref": "#/components/schemas/Groesse"})
+        );
+        assert!(actual["components"]["schemas"]["Groesse"].is_object());
+    }
+
+    #[test]
+    fn test_root_array() {
+        let hypothesis = generate_hypothesis(&json!([{"id": 1}]));
+
+        let actual = render_openapi(&hypothesis, &OpenApiOptions::default());
+
+        assert_eq!(
+            actual["components"]["schemas"]["Root"],
+            json!({"type": "array", "items": {"$ref": "#/components/schemas/RootItem"}})
+        );
+        assert_eq!(
+            actual["components"]["schemas"]["RootItem"]["required"],
+            json!(["id"])
+        );
+    }
+}
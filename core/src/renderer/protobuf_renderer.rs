@@ -0,0 +1,455 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::fmt::Write;
+
+use crate::model::NodeType;
+use crate::renderer::naming::{ascii, snake_case, TypeNames};
+use crate::SchemaHypothesis;
+
+const STRUCT_PROTO: &str = "google/protobuf/struct.proto";
+const TIMESTAMP_PROTO: &str = "google/protobuf/timestamp.proto";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtobufOptions {
+    /// The name of the root message; nested messages are named after their properties.
+    pub root_name: String,
+    /// The package of the messages.
+    pub package: Option<String>,
+    /// Field numbers overriding the alphabetical numbering, by message name and property, e.g.
+    /// `Order.orderId`; the members of a `oneof` are numbered consecutively from the given number.
+    pub field_numbers: BTreeMap<String, u32>,
+}
+
+impl Default for ProtobufOptions {
+    fn default() -> Self {
+        ProtobufOptions {
+            root_name: "Root".to_string(),
+            package: None,
+            field_numbers: BTreeMap::new(),
+        }
+    }
+}
+
+/// Renders a proto3 file with a message for the root (named `root_name`) and for every nested
+/// object. Fields are numbered in alphabetical order of their properties, unless overridden by
+/// [`ProtobufOptions::field_numbers`].
+///
+/// A root that is not an object is wrapped in a message with the single field `value`. Types
+/// that vary are rendered as `oneof`, except for lists and their items, which then are a
+/// `google.protobuf.Value`.
+#[must_use]
+pub fn render_protobuf(schema: &SchemaHypothesis, options: &ProtobufOptions) -> String {
+    let type_names = TypeNames::ascii(&schema.root, &options.root_name);
+    let mut renderer = ProtobufRenderer {
+        type_names: &type_names,
+        options,
+        imports: BTreeSet::new(),
+    };
+
+    let mut messages = Vec::new();
+    if !schema.root.is_object() {
+        let properties = [("value", &schema.root, true)];
+        messages.push(renderer.render_message(type_names.root_name(), &properties));
+    }
+    for (name, object) in type_names.objects() {
+        let properties: Vec<(&str, &NodeType, bool)> = object
+            .properties
+            .iter()
+            .map(|(key, property)| (key.as_str(), &property.node_type, property.required))
+            .collect();
+        messages.push(renderer.render_message(name, &properties));
+    }
+
+    let mut header = "syntax = \"proto3\";\n".to_string();
+    if let Some(package) = &options.package {
+        let _ = write!(header, "\npackage {package};\n");
+    }
+    if !renderer.imports.is_empty() {
+        header.push('\n');
+        for import in &renderer.imports {
+            let _ = writeln!(header, "import \"{import}\";");
+        }
+    }
+    messages.insert(0, header);
+    messages.join("\n")
+}
+
+/// How a property is declared in a message.
+enum Field {
+    /// A field with an optional label (`optional` or `repeated`).
+    Single {
+        label: &'static str,
+        field_type: String,
+        date: bool,
+    },
+    /// A `oneof` with a member for every type, as suffix of the member name and its type.
+    OneOf(Vec<(String, String)>),
+}
+
+impl Field {
+    fn numbers(&self) -> u32 {
+        match self {
+            Field::Single { .. } => 1,
+            Field::OneOf(members) => u32::try_from(members.len()).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+struct ProtobufRenderer<'a> {
+    type_names: &'a TypeNames<'a>,
+    options: &'a ProtobufOptions,
+    /// The well-known types imported.
+    imports: BTreeSet<&'static str>,
+}
+
+impl ProtobufRenderer<'_> {
+    /// Renders a message with a field for every property, as key, type and whether it is
+    /// required.
+    fn render_message(&mut self, name: &str, properties: &[(&str, &NodeType, bool)]) -> String {
+        let fields: Vec<(&str, Field)> = properties
+            .iter()
+            .map(|(key, node_type, required)| (*key, self.field(node_type, *required)))
+            .collect();
+
+        // overridden numbers are taken first, the others are assigned in order
+        let overrides: Vec<Option<u32>> = fields
+            .iter()
+            .map(|(key, _)| {
+                let field = format!("{name}.{key}");
+                self.options.field_numbers.get(&field).copied()
+            })
+            .collect();
+        let mut taken = BTreeSet::new();
+        for ((_, field), number) in fields.iter().zip(&overrides) {
+            if let Some(number) = number {
+                taken.extend(*number..number.saturating_add(field.numbers()));
+            }
+        }
+        let mut next = 0;
+        let mut next_number = || {
+            next += 1;
+            while taken.contains(&next) {
+                next += 1;
+            }
+            next
+        };
+
+        let mut names = BTreeSet::new();
+        let mut unique = |name: String| {
+            let mut unique = name.clone();
+            let mut suffix = 2;
+            while names.contains(&unique) {
+                unique = format!("{name}_{suffix}");
+                suffix += 1;
+            }
+            names.insert(unique.clone());
+            unique
+        };
+
+        let mut message = format!("message {name} {{\n");
+        for ((key, field), number) in fields.iter().zip(overrides) {
+            let field_name = unique(snake_case(&ascii(key)));
+            match field {
+                Field::Single {
+                    label,
+                    field_type,
+                    date,
+                } => {
+                    let number = number.unwrap_or_else(&mut next_number);
+                    if *date {
+                        message.push_str("  // ISO 8601 date, e.g. `2022-01-31`\n");
+                    }
+                    let json_name = if default_json_name(&field_name) == *key {
+                        String::new()
+                    } else {
+                        format!(" [json_name = {}]", serde_json::Value::from(*key))
+                    };
+                    let _ = writeln!(
+                        message,
+                        "  {label}{field_type} {field_name} = {number}{json_name};"
+                    );
+                }
+                Field::OneOf(members) => {
+                    let _ = writeln!(message, "  oneof {field_name} {{");
+                    for (i, (suffix, member_type)) in (0..).zip(members) {
+                        let number = match number {
+                            Some(number) => number + i,
+                            None => next_number(),
+                        };
+                        let member_name = unique(format!("{field_name}_{suffix}"));
+                        let _ = writeln!(message, "    {member_type} {member_name} = {number};");
+                    }
+                    message.push_str("  }\n");
+                }
+            }
+        }
+        message.push_str("}\n");
+        message
+    }
+
+    fn field(&mut self, node_type: &NodeType, required: bool) -> Field {
+        let label = if required { "" } else { "optional " };
+        match node_type {
+            NodeType::Array(array) => Field::Single {
+                label: "repeated ",
+                field_type: match &array.items {
+                    Some(items) => self.element_type(items),
+                    None => self.value_type("Value"),
+                },
+                date: matches!(array.items.as_deref(), Some(NodeType::Date(_))),
+            },
+            NodeType::Any(any) => {
+                let types: Vec<&NodeType> = any
+                    .nodes
+                    .iter()
+                    .filter(|node_type| **node_type != NodeType::Null)
+                    .collect();
+                match types.as_slice() {
+                    // `null` is the absence of an optional field
+                    [single] => self.field(single, false),
+                    [NodeType::Integer(_), NodeType::Number(_)] => Field::Single {
+                        label: if any.nodes.contains(&NodeType::Null) {
+                            "optional "
+                        } else {
+                            label
+                        },
+                        field_type: "double".to_string(),
+                        date: false,
+                    },
+                    [] => Field::Single {
+                        label: "optional ",
+                        field_type: self.value_type("Value"),
+                        date: false,
+                    },
+                    _ => Field::OneOf(
+                        types
+                            .into_iter()
+                            .map(|node_type| {
+                                (self.member_suffix(node_type), self.element_type(node_type))
+                            })
+                            .collect(),
+                    ),
+                }
+            }
+            node_type => Field::Single {
+                label,
+                field_type: self.element_type(node_type),
+                date: matches!(node_type, NodeType::Date(_)),
+            },
+        }
+    }
+
+    /// The type of `node_type` as a single value, i.e. not as a `repeated` field or `oneof`.
+    fn element_type(&mut self, node_type: &NodeType) -> String {
+        match node_type {
+            NodeType::String(_) | NodeType::Date(_) => "string".to_string(),
+            NodeType::DateTime(_) => {
+                self.imports.insert(TIMESTAMP_PROTO);
+                "google.protobuf.Timestamp".to_string()
+            }
+            NodeType::Integer(_) => "int64".to_string(),
+            NodeType::Number(_) => "double".to_string(),
+            NodeType::Boolean => "bool".to_string(),
+            NodeType::Object(object) => self.type_names.name(object).to_string(),
+            NodeType::Array(_) => self.value_type("ListValue"),
+            NodeType::Any(any)
+                if any.nodes.iter().all(|node_type| {
+                    matches!(node_type, NodeType::Integer(_) | NodeType::Number(_))
+                }) =>
+            {
+                "double".to_string()
+            }
+            NodeType::Null | NodeType::Any(_) => self.value_type("Value"),
+        }
+    }
+
+    fn value_type(&mut self, name: &str) -> String {
+        self.imports.insert(STRUCT_PROTO);
+        format!("google.protobuf.{name}")
+    }
+
+    fn member_suffix(&self, node_type: &NodeType) -> String {
+        match node_type {
+            NodeType::Boolean => "bool".to_string(),
+            NodeType::Integer(_) => "integer".to_string(),
+            NodeType::Number(_) => "number".to_string(),
+            NodeType::Date(_) => "date".to_string(),
+            NodeType::DateTime(_) => "date_time".to_string(),
+            NodeType::Array(_) => "list".to_string(),
+            NodeType::Object(object) => snake_case(self.type_names.name(object)),
+            _ => "string".to_string(),
+        }
+    }
+}
+
+/// The name of a field in JSON if none is given, which is the field name in lowerCamelCase, e.g.
+/// `orderId` for `order_id`.
+fn default_json_name(field_name: &str) -> String {
+    let mut json_name = String::new();
+    let mut capitalize = false;
+    for c in field_name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            json_name.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            json_name.push(c);
+        }
+    }
+    json_name
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::btreemap;
+    use serde_json::json;
+
+    use crate::renderer::protobuf_renderer::{render_protobuf, ProtobufOptions};
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_messages() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "orderId": "o-1",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "day": "2022-01-01",
+                "customer": {"name": "x"},
+                "tags": ["a"],
+                "voucher": null,
+                "total_amount": 1
+            })),
+            generate_hypothesis(&json!({
+                "orderId": "o-2",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "day": "2022-01-01",
+                "tags": [],
+                "voucher": "x",
+                "total_amount": 1.5
+            })),
+        );
+        let options = ProtobufOptions {
+            root_name: "Order".to_string(),
+            package: Some("shop.v1".to_string()),
+            ..ProtobufOptions::default()
+        };
+
+        let actual = render_protobuf(&hypothesis, &options);
+
+        assert_eq!(
+            actual,
+            r#"syntax = "proto3";
+
+package shop.v1;
+
+import "google/protobuf/timestamp.proto";
+
+message Order {
+  google.protobuf.Timestamp created_at = 1;
+  optional Customer customer = 2;
+  // ISO 8601 date, e.g. `2022-01-31`
+  string day = 3;
+  string order_id = 4;
+  repeated string tags = 5;
+  double total_amount = 6 [json_name = "total_amount"];
+  optional string voucher = 7;
+}
+
+message Customer {
+  string name = 1;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_oneof_and_field_numbers() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({"id": 1, "payload": {"a": 1}, "values": [1, "a"]})),
+            generate_hypothesis(&json!({"id": "x", "payload": [], "values": [null]})),
+        );
+        let options = ProtobufOptions {
+            field_numbers: btreemap! {
+                "Root.id".to_string() => 10,
+                "Root.values".to_string() => 1,
+            },
+            ..ProtobufOptions::default()
+        };
+
+        let actual = render_protobuf(&hypothesis, &options);
+
+        assert_eq!(
+            actual,
+            r#"syntax = "proto3";
+
+import "google/protobuf/struct.proto";
+
+message Root {
+  oneof id {
+    int64 id_integer = 10;
+    string id_string = 11;
+  }
+  oneof payload {
+    google.protobuf.ListValue payload_list = 2;
+    Payload payload_payload = 3;
+  }
+  repeated google.protobuf.Value values = 1;
+}
+
+message Payload {
+  int64 a = 1;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_root_list() {
+        let hypothesis = generate_hypothesis(&json!(["2022-01-01T00:00:00Z"]));
+
+        let actual = render_protobuf(&hypothesis, &ProtobufOptions::default());
+
+        assert_eq!(
+            actual,
+            r#"syntax = "proto3";
+
+import "google/protobuf/timestamp.proto";
+
+message Root {
+  repeated google.protobuf.Timestamp value = 1;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_nullable_number_and_non_ascii_keys() {
+        let hypothesis = [
+            json!({"a": 1, "größe": {"b": 1}}),
+            json!({"a": 1.5}),
+            json!({"a": null}),
+        ]
+        .iter()
+        .map(generate_hypothesis)
+        .reduce(merge_hypothesis)
+        .unwrap();
+
+        let actual = render_protobuf(&hypothesis, &ProtobufOptions::default());
+
+        assert_eq!(
+            actual,
+            r#"syntax = "proto3";
+
+message Root {
+  optional double a = 1;
+  optional Groesse groesse = 2 [json_name = "größe"];
+}
+
+message Groesse {
+  int64 b = 1;
+}
+"#
+        );
+    }
+}
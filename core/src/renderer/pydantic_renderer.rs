@@ -0,0 +1,387 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::model::{NodeType, ObjectNode};
+use crate::renderer::naming::{snake_case, TypeNames};
+use crate::SchemaHypothesis;
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Names used by the rendered source, which must be neither shadowed by classes nor by fields.
+const RESERVED: &[&str] = &[
+    "Any",
+    "BaseModel",
+    "ConfigDict",
+    "Field",
+    "List",
+    "Optional",
+    "RootModel",
+    "Union",
+    "bool",
+    "dt",
+    "float",
+    "int",
+    "str",
+];
+
+/// Attributes of `BaseModel` that fields must not shadow.
+const MODEL_ATTRIBUTES: &[&str] = &[
+    "construct",
+    "copy",
+    "dict",
+    "from_orm",
+    "json",
+    "parse_file",
+    "parse_obj",
+    "parse_raw",
+    "schema",
+    "schema_json",
+    "update_forward_refs",
+    "validate",
+];
+
+/// Renders Python source declaring a pydantic (v2) model for the root type (named `root_name`)
+/// and for every nested object, named after the property it is found at. Models are declared
+/// before they are used, so no forward references have to be resolved.
+///
+/// Properties whose key is no valid field name, e.g. a keyword or a key with dashes, are renamed
+/// and keep their key as alias.
+#[must_use]
+pub fn render_pydantic(schema: &SchemaHypothesis, root_name: &str) -> String {
+    let mut reserved: Vec<&str> = KEYWORDS.to_vec();
+    reserved.extend(RESERVED);
+    let type_names = TypeNames::reserving(&schema.root, root_name, &reserved);
+    let mut renderer = PydanticRenderer {
+        type_names: &type_names,
+        class_names: type_names.objects().map(|(name, _)| name).collect(),
+        typing: BTreeSet::new(),
+        pydantic: BTreeSet::new(),
+        datetime: false,
+    };
+
+    let mut declarations = Vec::new();
+    let mut declared = BTreeSet::new();
+    for (_, object) in type_names.objects() {
+        renderer.declare(object, &mut declared, &mut declarations);
+    }
+    if !schema.root.is_object() {
+        renderer.pydantic.insert("RootModel");
+        let root_type = renderer.type_of(&schema.root);
+        declarations.push(format!(
+            "class {}(RootModel[{root_type}]):\n    pass\n",
+            type_names.root_name()
+        ));
+    }
+
+    let mut imports = String::new();
+    if renderer.datetime {
+        imports.push_str("import datetime as dt\n");
+    }
+    if !renderer.typing.is_empty() {
+        let typing: Vec<&str> = renderer.typing.iter().copied().collect();
+        let _ = writeln!(imports, "from typing import {}", typing.join(", "));
+    }
+    if !imports.is_empty() {
+        imports.push('\n');
+    }
+    let pydantic: Vec<&str> = renderer.pydantic.iter().copied().collect();
+    let _ = writeln!(imports, "from pydantic import {}", pydantic.join(", "));
+    declarations.insert(0, imports);
+    declarations.join("\n\n")
+}
+
+struct PydanticRenderer<'a> {
+    type_names: &'a TypeNames<'a>,
+    /// The names of all models, which fields must not shadow.
+    class_names: BTreeSet<&'a str>,
+    /// The names imported from `typing` and `pydantic`.
+    typing: BTreeSet<&'static str>,
+    pydantic: BTreeSet<&'static str>,
+    /// Whether the `datetime` module is used.
+    datetime: bool,
+}
+
+impl<'a> PydanticRenderer<'a> {
+    /// Declares the models `object` depends on, then `object` itself.
+    fn declare(
+        &mut self,
+        object: &'a ObjectNode,
+        declared: &mut BTreeSet<*const ObjectNode>,
+        declarations: &mut Vec<String>,
+    ) {
+        // by address, as objects of the same structure are distinct models
+        if !declared.insert(object) {
+            return;
+        }
+        let mut dependencies = Vec::new();
+        for property in object.properties.values() {
+            collect_objects(&property.node_type, &mut dependencies);
+        }
+        for dependency in dependencies {
+            self.declare(dependency, declared, declarations);
+        }
+        declarations.push(self.render_model(self.type_names.name(object), object));
+    }
+
+    fn render_model(&mut self, name: &str, object: &ObjectNode) -> String {
+        self.pydantic.insert("BaseModel");
+        let mut model = format!("class {name}(BaseModel):\n");
+        let mut lines = Vec::new();
+        let mut fields = BTreeSet::new();
+        let mut aliased = false;
+        for (key, property) in &object.properties {
+            let mut field = key.clone();
+            if !self.is_field_name(key) {
+                field = snake_case(key);
+                if field.starts_with("model_") {
+                    // a suffix would not leave the namespace of pydantic's own attributes
+                    field = format!("field_{field}");
+                } else if !self.is_field_name(&field) {
+                    field.push('_');
+                }
+            }
+            let base = field.clone();
+            let mut suffix = 2;
+            while fields.contains(&field) {
+                field = format!("{base}_{suffix}");
+                suffix += 1;
+            }
+            fields.insert(field.clone());
+
+            let mut field_type = self.type_of(&property.node_type);
+            if !property.required && !field_type.starts_with("Optional[") {
+                self.typing.insert("Optional");
+                field_type = format!("Optional[{field_type}]");
+            }
+            let default = if field == *key {
+                if property.required { "" } else { " = None" }.to_string()
+            } else {
+                aliased = true;
+                self.pydantic.insert("Field");
+                let alias = serde_json::Value::from(key.as_str());
+                if property.required {
+                    format!(" = Field(alias={alias})")
+                } else {
+                    format!(" = Field(default=None, alias={alias})")
+                }
+            };
+            lines.push(format!("    {field}: {field_type}{default}\n"));
+        }
+
+        if aliased {
+            // allow to construct models by field name as well
+            self.pydantic.insert("ConfigDict");
+            model.push_str("    model_config = ConfigDict(populate_by_name=True)\n\n");
+        }
+        if lines.is_empty() {
+            model.push_str("    pass\n");
+        }
+        for line in lines {
+            model.push_str(&line);
+        }
+        model
+    }
+
+    /// Whether `name` can be used as field name as is; names starting with an underscore are
+    /// private attributes in pydantic.
+    fn is_field_name(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        chars.next().is_some_and(char::is_alphabetic)
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+            && !KEYWORDS.contains(&name)
+            && !RESERVED.contains(&name)
+            && !MODEL_ATTRIBUTES.contains(&name)
+            && !name.starts_with("model_")
+            && !self.class_names.contains(name)
+    }
+
+    fn type_of(&mut self, node_type: &NodeType) -> String {
+        match node_type {
+            NodeType::String(_) => "str".to_string(),
+            NodeType::Date(_) => {
+                self.datetime = true;
+                "dt.date".to_string()
+            }
+            NodeType::DateTime(_) => {
+                self.datetime = true;
+                "dt.datetime".to_string()
+            }
+            NodeType::Integer(_) => "int".to_string(),
+            NodeType::Number(_) => "float".to_string(),
+            NodeType::Boolean => "bool".to_string(),
+            NodeType::Null => "None".to_string(),
+            NodeType::Array(array) => {
+                self.typing.insert("List");
+                match &array.items {
+                    Some(items) => format!("List[{}]", self.type_of(items)),
+                    None => {
+                        self.typing.insert("Any");
+                        "List[Any]".to_string()
+                    }
+                }
+            }
+            NodeType::Object(object) => self.type_names.name(object).to_string(),
+            NodeType::Any(any) => {
+                let types: Vec<String> = any
+                    .nodes
+                    .iter()
+                    .filter(|node_type| **node_type != NodeType::Null)
+                    .map(|node_type| self.type_of(node_type))
+                    .collect();
+                let union = if types.len() == 1 {
+                    types.into_iter().next().unwrap_or_default()
+                } else {
+                    self.typing.insert("Union");
+                    format!("Union[{}]", types.join(", "))
+                };
+                if any.nodes.contains(&NodeType::Null) {
+                    self.typing.insert("Optional");
+                    format!("Optional[{union}]")
+                } else {
+                    union
+                }
+            }
+        }
+    }
+}
+
+/// Collects the objects `node_type` refers to, without the objects these refer to.
+fn collect_objects<'a>(node_type: &'a NodeType, objects: &mut Vec<&'a ObjectNode>) {
+    match node_type {
+        NodeType::Object(object) => objects.push(object),
+        NodeType::Array(array) => {
+            if let Some(items) = &array.items {
+                collect_objects(items, objects);
+            }
+        }
+        NodeType::Any(any) => {
+            for node_type in &any.nodes {
+                collect_objects(node_type, objects);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::pydantic_renderer::render_pydantic;
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_models() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "orderId": "o-1",
+                "created-at": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x", "class": "a"},
+                "items": [{"price": 1}],
+                "id": 1,
+                "note": null
+            })),
+            generate_hypothesis(&json!({
+                "orderId": "o-2",
+                "created-at": "2022-01-01T00:00:00Z",
+                "items": [],
+                "id": "2",
+                "note": "x",
+                "json": true,
+                "str": "x",
+                "model_name": "m"
+            })),
+        );
+
+        let actual = render_pydantic(&hypothesis, "order");
+
+        assert_eq!(
+            actual,
+            r#"import datetime as dt
+from typing import List, Optional, Union
+
+from pydantic import BaseModel, ConfigDict, Field
+
+
+class Customer(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    class_: str = Field(alias="class")
+    name: str
+
+
+class ItemsItem(BaseModel):
+    price: int
+
+
+class Order(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    created_at: dt.datetime = Field(alias="created-at")
+    customer: Optional[Customer] = None
+    id: Union[int, str]
+    items: List[ItemsItem]
+    json_: Optional[bool] = Field(default=None, alias="json")
+    field_model_name: Optional[str] = Field(default=None, alias="model_name")
+    note: Optional[str]
+    orderId: str
+    str_: Optional[str] = Field(default=None, alias="str")
+"#
+        );
+    }
+
+    #[test]
+    fn test_root_model() {
+        let hypothesis = generate_hypothesis(&json!([{"day": "2022-01-01"}, null]));
+
+        let actual = render_pydantic(&hypothesis, "Root");
+
+        assert_eq!(
+            actual,
+            r#"import datetime as dt
+from typing import List, Optional
+
+from pydantic import BaseModel, RootModel
+
+
+class RootItem(BaseModel):
+    day: dt.date
+
+
+class Root(RootModel[List[Optional[RootItem]]]):
+    pass
+"#
+        );
+    }
+
+    #[test]
+    fn test_objects_of_the_same_structure() {
+        let hypothesis = generate_hypothesis(&json!({"buyer": {"id": 1}, "seller": {"id": 2}}));
+
+        let actual = render_pydantic(&hypothesis, "Deal");
+
+        assert_eq!(
+            actual,
+            r#"from pydantic import BaseModel
+
+
+class Buyer(BaseModel):
+    id: int
+
+
+class Seller(BaseModel):
+    id: int
+
+
+class Deal(BaseModel):
+    buyer: Buyer
+    seller: Seller
+"#
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::model::{NodeType, ObjectNode};
+use crate::renderer::naming::{pascal_case, snake_case, TypeNames};
+use crate::SchemaHypothesis;
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be used as raw identifiers.
+const PATH_KEYWORDS: &[&str] = &["crate", "self", "super", "Self"];
+
+/// The prelude, serde and chrono types the structs refer to by their plain names, e.g. a struct
+/// named `Option` would break every optional field of the module, and the keyword `Self`, which
+/// cannot be a raw identifier.
+const RESERVED: &[&str] = &[
+    "Box",
+    "DateTime",
+    "Deserialize",
+    "FixedOffset",
+    "NaiveDate",
+    "Option",
+    "Self",
+    "Serialize",
+    "String",
+    "Value",
+    "Vec",
+];
+
+const DERIVES: &str = "#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]";
+
+/// Renders Rust source declaring a struct (deriving serde's `Serialize` and `Deserialize`) for
+/// the root type (named `root_name`) and for every nested object, named after the property it is
+/// found at. Types that vary are declared as untagged enums; dates and date-times are `chrono`
+/// types, which requires the `serde` feature of `chrono`.
+#[must_use]
+pub fn render_rust(schema: &SchemaHypothesis, root_name: &str) -> String {
+    let type_names = TypeNames::reserving(&schema.root, root_name, RESERVED);
+    let mut renderer = RustRenderer {
+        type_names: &type_names,
+        taken: type_names
+            .objects()
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        enums: Vec::new(),
+        chrono_types: BTreeSet::new(),
+    };
+    renderer.taken.insert(type_names.root_name().to_string());
+    renderer
+        .taken
+        .extend(RESERVED.iter().map(|name| (*name).to_string()));
+
+    let mut declarations = Vec::new();
+    if !schema.root.is_object() {
+        let root_name = type_names.root_name();
+        let root_type = renderer.type_of(&schema.root, &format!("{root_name}Value"));
+        declarations.push(format!("pub type {root_name} = {root_type};\n"));
+        declarations.append(&mut renderer.enums);
+    }
+    for (name, object) in type_names.objects() {
+        declarations.push(renderer.render_struct(name, object));
+        declarations.append(&mut renderer.enums);
+    }
+
+    let mut imports = String::new();
+    if !renderer.chrono_types.is_empty() {
+        let chrono_types: Vec<&str> = renderer.chrono_types.iter().copied().collect();
+        let _ = writeln!(imports, "use chrono::{{{}}};", chrono_types.join(", "));
+    }
+    imports.push_str("use serde::{Deserialize, Serialize};\n");
+    declarations.insert(0, imports);
+    declarations.join("\n")
+}
+
+struct RustRenderer<'a> {
+    type_names: &'a TypeNames<'a>,
+    /// Names of all declared types, to keep the names of the enums unique.
+    taken: BTreeSet<String>,
+    /// Enums declared while rendering the current type, to be declared after it.
+    enums: Vec<String>,
+    /// The `chrono` types used, to be imported.
+    chrono_types: BTreeSet<&'static str>,
+}
+
+impl RustRenderer<'_> {
+    fn render_struct(&mut self, name: &str, object: &ObjectNode) -> String {
+        let mut declaration = format!("{DERIVES}\npub struct {name} {{\n");
+        let mut fields = BTreeSet::new();
+        for (key, property) in &object.properties {
+            let mut field = snake_case(key);
+            let mut suffix = 2;
+            while fields.contains(&field) {
+                field = format!("{}_{suffix}", snake_case(key));
+                suffix += 1;
+            }
+            fields.insert(field.clone());
+
+            let enum_name = format!("{name}{}", pascal_case(key));
+            let mut field_type = self.type_of(&property.node_type, &enum_name);
+            let field = identifier(&field);
+            let mut attributes = Vec::new();
+            if field.trim_start_matches("r#") != key {
+                attributes.push(format!(
+                    "rename = {}",
+                    serde_json::Value::from(key.as_str())
+                ));
+            }
+            if !property.required {
+                attributes.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+                if !field_type.starts_with("Option<") {
+                    field_type = format!("Option<{field_type}>");
+                }
+            }
+
+            if !attributes.is_empty() {
+                let _ = writeln!(declaration, "    #[serde({})]", attributes.join(", "));
+            }
+            let _ = writeln!(declaration, "    pub {field}: {field_type},");
+        }
+        declaration.push_str("}\n");
+        declaration
+    }
+
+    /// The Rust type of `node_type`; `name` is the name of the enum declared if the type varies.
+    fn type_of(&mut self, node_type: &NodeType, name: &str) -> String {
+        match node_type {
+            NodeType::String(_) => "String".to_string(),
+            NodeType::Date(_) => {
+                self.chrono_types.insert("NaiveDate");
+                "NaiveDate".to_string()
+            }
+            NodeType::DateTime(_) => {
+                self.chrono_types.extend(["DateTime", "FixedOffset"]);
+                "DateTime<FixedOffset>".to_string()
+            }
+            NodeType::Integer(_) => "i64".to_string(),
+            NodeType::Number(_) => "f64".to_string(),
+            NodeType::Boolean => "bool".to_string(),
+            NodeType::Null => "Option<serde_json::Value>".to_string(),
+            NodeType::Array(array) => match &array.items {
+                Some(items) => format!("Vec<{}>", self.type_of(items, &format!("{name}Item"))),
+                None => "Vec<serde_json::Value>".to_string(),
+            },
+            NodeType::Object(object) => self.type_names.name(object).to_string(),
+            NodeType::Any(any) => {
+                let nullable = any.nodes.contains(&NodeType::Null);
+                let mut variants: Vec<&NodeType> = any
+                    .nodes
+                    .iter()
+                    .filter(|node_type| **node_type != NodeType::Null)
+                    .collect();
+                // untagged enums take the first variant that matches, so the more specific first
+                variants.sort_by_key(|node_type| variant_rank(node_type));
+
+                let variant_type = match variants.as_slice() {
+                    [] => return "serde_json::Value".to_string(),
+                    [single] => self.type_of(single, name),
+                    [NodeType::Integer(_), NodeType::Number(_)] => "f64".to_string(),
+                    _ => self.render_enum(&variants, name),
+                };
+                if nullable {
+                    format!("Option<{variant_type}>")
+                } else {
+                    variant_type
+                }
+            }
+        }
+    }
+
+    fn render_enum(&mut self, variants: &[&NodeType], name: &str) -> String {
+        let mut enum_name = name.to_string();
+        let mut suffix = 2;
+        while self.taken.contains(&enum_name) {
+            enum_name = format!("{name}{suffix}");
+            suffix += 1;
+        }
+        self.taken.insert(enum_name.clone());
+
+        let mut declaration = format!("{DERIVES}\n#[serde(untagged)]\npub enum {enum_name} {{\n");
+        let mut variant_names = BTreeSet::new();
+        for node_type in variants {
+            let variant_name = match node_type {
+                // objects come last, their name may be taken by another variant, e.g. `Integer`
+                NodeType::Object(object) => {
+                    let name = self.type_names.name(object);
+                    if variant_names.contains(name) {
+                        "Object".to_string()
+                    } else {
+                        name.to_string()
+                    }
+                }
+                NodeType::Array(_) => "Array".to_string(),
+                NodeType::Boolean => "Boolean".to_string(),
+                NodeType::Integer(_) => "Integer".to_string(),
+                NodeType::Number(_) => "Number".to_string(),
+                NodeType::Date(_) => "Date".to_string(),
+                NodeType::DateTime(_) => "DateTime".to_string(),
+                _ => "String".to_string(),
+            };
+            variant_names.insert(variant_name.clone());
+            let variant_type = self.type_of(node_type, &format!("{enum_name}Item"));
+            let _ = writeln!(declaration, "    {variant_name}({variant_type}),");
+        }
+        declaration.push_str("}\n");
+        self.enums.push(declaration);
+        enum_name
+    }
+}
+
+fn variant_rank(node_type: &NodeType) -> u8 {
+    match node_type {
+        NodeType::Boolean => 0,
+        NodeType::Integer(_) => 1,
+        NodeType::Number(_) => 2,
+        NodeType::Date(_) => 3,
+        NodeType::DateTime(_) => 4,
+        NodeType::String(_) => 5,
+        NodeType::Array(_) => 6,
+        _ => 7,
+    }
+}
+
+fn identifier(field: &str) -> String {
+    if PATH_KEYWORDS.contains(&field) {
+        format!("{field}_")
+    } else if KEYWORDS.contains(&field) {
+        format!("r#{field}")
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::rust_renderer::render_rust;
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_structs() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "id": 1,
+                "type": "a",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x"},
+                "score": 1,
+                "tags": []
+            })),
+            generate_hypothesis(&json!({
+                "id": "2",
+                "type": "b",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "score": 1.5,
+                "tags": [],
+                "note": null
+            })),
+        );
+
+        let actual = render_rust(&hypothesis, "event");
+
+        assert_eq!(
+            actual,
+            r#"use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer: Option<Customer>,
+    pub id: EventId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<serde_json::Value>,
+    pub score: f64,
+    pub tags: Vec<serde_json::Value>,
+    pub r#type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EventId {
+    Integer(i64),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Customer {
+    pub name: String,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_root_enum() {
+        let hypothesis = generate_hypothesis(&json!([1, "a", null]));
+
+        let actual = render_rust(&hypothesis, "Root");
+
+        assert_eq!(
+            actual,
+            r#"use serde::{Deserialize, Serialize};
+
+pub type Root = Vec<Option<RootValueItem>>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RootValueItem {
+    Integer(i64),
+    String(String),
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_reserved_names() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({"integer": 1})),
+            generate_hypothesis(&json!({"integer": {"a": 1}})),
+        );
+
+        let actual = render_rust(&hypothesis, "string");
+
+        assert_eq!(
+            actual,
+            r#"use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct String2 {
+    pub integer: String2Integer,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum String2Integer {
+    Integer(i64),
+    Object(Integer),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Integer {
+    pub a: i64,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_self() {
+        let hypothesis = generate_hypothesis(&json!({"self": {"href": "x"}}));
+
+        let actual = render_rust(&hypothesis, "Root");
+
+        assert!(actual.contains("pub self_: RootSelf,"), "{}", actual);
+        assert!(actual.contains("pub struct RootSelf {"), "{}", actual);
+    }
+}
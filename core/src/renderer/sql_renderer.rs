@@ -0,0 +1,433 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use maplit::btreemap;
+
+use crate::model::{NodeType, ObjectNode, ObjectProperty};
+use crate::renderer::naming::snake_case;
+use crate::SchemaHypothesis;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    fn string(self) -> &'static str {
+        "TEXT"
+    }
+
+    fn integer(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres | SqlDialect::MySql => "BIGINT",
+            SqlDialect::Sqlite => "INTEGER",
+        }
+    }
+
+    fn number(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "DOUBLE PRECISION",
+            SqlDialect::MySql => "DOUBLE",
+            SqlDialect::Sqlite => "REAL",
+        }
+    }
+
+    fn boolean(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres | SqlDialect::MySql => "BOOLEAN",
+            SqlDialect::Sqlite => "INTEGER",
+        }
+    }
+
+    fn date(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres | SqlDialect::MySql => "DATE",
+            SqlDialect::Sqlite => "TEXT",
+        }
+    }
+
+    fn date_time(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "TIMESTAMPTZ",
+            SqlDialect::MySql => "DATETIME(3)",
+            SqlDialect::Sqlite => "TEXT",
+        }
+    }
+
+    fn json(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "JSONB",
+            SqlDialect::MySql => "JSON",
+            SqlDialect::Sqlite => "TEXT",
+        }
+    }
+
+    fn quote(self, identifier: &str) -> String {
+        match self {
+            SqlDialect::Postgres | SqlDialect::Sqlite => {
+                format!("\"{}\"", identifier.replace('"', "\"\""))
+            }
+            SqlDialect::MySql => format!("`{}`", identifier.replace('`', "``")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SqlOptions {
+    /// The name of the table of the root object; child tables are prefixed with it.
+    pub table_name: String,
+    pub dialect: SqlDialect,
+    /// The separator between the names of nested objects and their properties in column names,
+    /// and between the names of parent and child tables.
+    pub separator: String,
+}
+
+impl Default for SqlOptions {
+    fn default() -> Self {
+        SqlOptions {
+            table_name: "root".to_string(),
+            dialect: SqlDialect::Postgres,
+            separator: "_".to_string(),
+        }
+    }
+}
+
+/// Renders `CREATE TABLE` statements for a table of the root object, with the properties of
+/// nested objects flattened into prefixed columns, and a child table for every array of objects,
+/// referring to its parent with `_parent_id` and ordered by `_position`. Every table has the
+/// primary key `_id`.
+///
+/// Columns are `NOT NULL` if their property is required and never `null`. Properties of varying
+/// types, empty objects and arrays of other types than objects are stored as JSON.
+#[must_use]
+pub fn render_sql(schema: &SchemaHypothesis, options: &SqlOptions) -> String {
+    let mut renderer = SqlRenderer {
+        options,
+        table_names: BTreeSet::new(),
+        tables: Vec::new(),
+    };
+    let table_name = renderer.unique_table_name(snake_case(&options.table_name));
+    match &schema.root {
+        NodeType::Object(root) if !root.properties.is_empty() => {
+            renderer.render_table(table_name, None, root);
+        }
+        // a table needs columns, so any other root is the column `value`
+        root => {
+            let root = ObjectNode::new(btreemap! {
+                "value".to_string() => ObjectProperty::new(root.clone()),
+            });
+            renderer.render_table(table_name, None, &root);
+        }
+    }
+    renderer.tables.join("\n")
+}
+
+struct SqlRenderer<'a> {
+    options: &'a SqlOptions,
+    table_names: BTreeSet<String>,
+    /// The rendered statements, parents before their children.
+    tables: Vec<String>,
+}
+
+/// A column as name, type and whether it is `NOT NULL`.
+type Column = (String, &'static str, bool);
+
+impl<'a> SqlRenderer<'a> {
+    fn render_table(&mut self, name: String, parent: Option<&str>, object: &ObjectNode) {
+        let dialect = self.options.dialect;
+        let mut columns = Vec::new();
+        let mut children = Vec::new();
+        self.collect_columns(object, "", true, &mut columns, &mut children);
+
+        let mut definitions = vec![format!(
+            "{} {} PRIMARY KEY",
+            dialect.quote("_id"),
+            dialect.integer()
+        )];
+        if parent.is_some() {
+            definitions.push(format!(
+                "{} {} NOT NULL",
+                dialect.quote("_parent_id"),
+                dialect.integer()
+            ));
+            definitions.push(format!(
+                "{} {} NOT NULL",
+                dialect.quote("_position"),
+                dialect.integer()
+            ));
+        }
+        let mut column_names = BTreeSet::new();
+        for (column, column_type, not_null) in columns {
+            let mut unique = column.clone();
+            let mut suffix = 2;
+            while column_names.contains(&unique) {
+                unique = format!("{column}{}{suffix}", self.options.separator);
+                suffix += 1;
+            }
+            column_names.insert(unique.clone());
+            let not_null = if not_null { " NOT NULL" } else { "" };
+            definitions.push(format!(
+                "{} {column_type}{not_null}",
+                dialect.quote(&unique)
+            ));
+        }
+        if let Some(parent) = parent {
+            definitions.push(format!(
+                "FOREIGN KEY ({}) REFERENCES {} ({})",
+                dialect.quote("_parent_id"),
+                dialect.quote(parent),
+                dialect.quote("_id")
+            ));
+        }
+
+        let mut table = format!("CREATE TABLE {} (\n", dialect.quote(&name));
+        let _ = writeln!(table, "    {}", definitions.join(",\n    "));
+        table.push_str(");\n");
+        self.tables.push(table);
+
+        for (column, child) in children {
+            let child_name =
+                self.unique_table_name(format!("{name}{}{column}", self.options.separator));
+            self.render_table(child_name, Some(&name), child);
+        }
+    }
+
+    /// Collects the columns of the properties of `object`, with the columns of nested objects
+    /// prefixed by `prefix`, and the arrays of objects, which become child tables.
+    fn collect_columns<'o>(
+        &self,
+        object: &'o ObjectNode,
+        prefix: &str,
+        not_null: bool,
+        columns: &mut Vec<Column>,
+        children: &mut Vec<(String, &'o ObjectNode)>,
+    ) {
+        for (key, property) in &object.properties {
+            let name = format!("{prefix}{}", snake_case(key));
+            let not_null = not_null && property.required;
+            self.collect_column(&property.node_type, name, not_null, columns, children);
+        }
+    }
+
+    fn collect_column<'o>(
+        &self,
+        node_type: &'o NodeType,
+        name: String,
+        not_null: bool,
+        columns: &mut Vec<Column>,
+        children: &mut Vec<(String, &'o ObjectNode)>,
+    ) {
+        let dialect = self.options.dialect;
+        let column_type = match node_type {
+            // an object without properties would have no columns
+            NodeType::Object(object) if object.properties.is_empty() => dialect.json(),
+            NodeType::Object(object) => {
+                let prefix = format!("{name}{}", self.options.separator);
+                return self.collect_columns(object, &prefix, not_null, columns, children);
+            }
+            NodeType::Array(array) => match array.items.as_deref().and_then(object_of) {
+                Some(items) => return children.push((name, items)),
+                None => dialect.json(),
+            },
+            NodeType::Any(any) => {
+                let not_null = not_null && !any.nodes.contains(&NodeType::Null);
+                let types: Vec<&NodeType> = any
+                    .nodes
+                    .iter()
+                    .filter(|node_type| **node_type != NodeType::Null)
+                    .collect();
+                match types.as_slice() {
+                    [single] => {
+                        return self.collect_column(single, name, not_null, columns, children)
+                    }
+                    [NodeType::Integer(_), NodeType::Number(_)] => {
+                        return columns.push((name, dialect.number(), not_null))
+                    }
+                    _ => return columns.push((name, dialect.json(), not_null)),
+                }
+            }
+            NodeType::String(_) => dialect.string(),
+            NodeType::Date(_) => dialect.date(),
+            NodeType::DateTime(_) => dialect.date_time(),
+            NodeType::Integer(_) => dialect.integer(),
+            NodeType::Number(_) => dialect.number(),
+            NodeType::Boolean => dialect.boolean(),
+            NodeType::Null => return columns.push((name, dialect.json(), false)),
+        };
+        columns.push((name, column_type, not_null));
+    }
+
+    fn unique_table_name(&mut self, name: String) -> String {
+        let mut unique = name.clone();
+        let mut suffix = 2;
+        while self.table_names.contains(&unique) {
+            unique = format!("{name}{}{suffix}", self.options.separator);
+            suffix += 1;
+        }
+        self.table_names.insert(unique.clone());
+        unique
+    }
+}
+
+/// The object of the items of an array that become rows of a child table, which may be `null`;
+/// objects without properties are no rows.
+fn object_of(items: &NodeType) -> Option<&ObjectNode> {
+    let object = match items {
+        NodeType::Object(object) => Some(object),
+        NodeType::Any(any) if any.nodes.len() == 2 && any.nodes.contains(&NodeType::Null) => {
+            any.nodes.iter().find_map(|node_type| match node_type {
+                NodeType::Object(object) => Some(object),
+                _ => None,
+            })
+        }
+        _ => None,
+    };
+    object.filter(|object| !object.properties.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::sql_renderer::{render_sql, SqlDialect, SqlOptions};
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    fn hypothesis() -> crate::SchemaHypothesis {
+        merge_hypothesis(
+            generate_hypothesis(&json!({
+                "orderId": "o-1",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "customer": {"id": 1, "address": {"city": "x"}},
+                "items": [{"sku": "a", "tags": ["x"]}],
+                "paid": true,
+                "extra": 1
+            })),
+            generate_hypothesis(&json!({
+                "orderId": "o-2",
+                "createdAt": "2022-01-01T00:00:00Z",
+                "customer": {"id": 2},
+                "items": [],
+                "paid": false,
+                "extra": "x",
+                "day": "2022-01-01"
+            })),
+        )
+    }
+
+    #[test]
+    fn test_postgres() {
+        let options = SqlOptions {
+            table_name: "Order".to_string(),
+            ..SqlOptions::default()
+        };
+
+        let actual = render_sql(&hypothesis(), &options);
+
+        assert_eq!(
+            actual,
+            r#"CREATE TABLE "order" (
+    "_id" BIGINT PRIMARY KEY,
+    "created_at" TIMESTAMPTZ NOT NULL,
+    "customer_address_city" TEXT,
+    "customer_id" BIGINT NOT NULL,
+    "day" DATE,
+    "extra" JSONB NOT NULL,
+    "order_id" TEXT NOT NULL,
+    "paid" BOOLEAN NOT NULL
+);
+
+CREATE TABLE "order_items" (
+    "_id" BIGINT PRIMARY KEY,
+    "_parent_id" BIGINT NOT NULL,
+    "_position" BIGINT NOT NULL,
+    "sku" TEXT NOT NULL,
+    "tags" JSONB NOT NULL,
+    FOREIGN KEY ("_parent_id") REFERENCES "order" ("_id")
+);
+"#
+        );
+    }
+
+    #[test]
+    fn test_mysql() {
+        let options = SqlOptions {
+            table_name: "order".to_string(),
+            dialect: SqlDialect::MySql,
+            separator: "__".to_string(),
+        };
+
+        let actual = render_sql(&hypothesis(), &options);
+
+        assert_eq!(
+            actual,
+            r#"CREATE TABLE `order` (
+    `_id` BIGINT PRIMARY KEY,
+    `created_at` DATETIME(3) NOT NULL,
+    `customer__address__city` TEXT,
+    `customer__id` BIGINT NOT NULL,
+    `day` DATE,
+    `extra` JSON NOT NULL,
+    `order_id` TEXT NOT NULL,
+    `paid` BOOLEAN NOT NULL
+);
+
+CREATE TABLE `order__items` (
+    `_id` BIGINT PRIMARY KEY,
+    `_parent_id` BIGINT NOT NULL,
+    `_position` BIGINT NOT NULL,
+    `sku` TEXT NOT NULL,
+    `tags` JSON NOT NULL,
+    FOREIGN KEY (`_parent_id`) REFERENCES `order` (`_id`)
+);
+"#
+        );
+    }
+
+    #[test]
+    fn test_sqlite_root_array() {
+        let hypothesis = generate_hypothesis(&json!([{"id": 1, "day": "2022-01-01"}]));
+        let options = SqlOptions {
+            dialect: SqlDialect::Sqlite,
+            ..SqlOptions::default()
+        };
+
+        let actual = render_sql(&hypothesis, &options);
+
+        assert_eq!(
+            actual,
+            r#"CREATE TABLE "root" (
+    "_id" INTEGER PRIMARY KEY
+);
+
+CREATE TABLE "root_value" (
+    "_id" INTEGER PRIMARY KEY,
+    "_parent_id" INTEGER NOT NULL,
+    "_position" INTEGER NOT NULL,
+    "day" TEXT NOT NULL,
+    "id" INTEGER NOT NULL,
+    FOREIGN KEY ("_parent_id") REFERENCES "root" ("_id")
+);
+"#
+        );
+    }
+
+    #[test]
+    fn test_empty_objects() {
+        let hypothesis = generate_hypothesis(&json!({"id": 1, "meta": {}, "items": [{}]}));
+
+        let actual = render_sql(&hypothesis, &SqlOptions::default());
+
+        assert_eq!(
+            actual,
+            r#"CREATE TABLE "root" (
+    "_id" BIGINT PRIMARY KEY,
+    "id" BIGINT NOT NULL,
+    "items" JSONB NOT NULL,
+    "meta" JSONB NOT NULL
+);
+"#
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt::Write;
+
+use crate::model::{NodeType, ObjectNode};
+use crate::renderer::naming::TypeNames;
+use crate::SchemaHypothesis;
+
+/// Global types of JavaScript and TypeScript, which must not be shadowed by the declared types.
+const RESERVED: &[&str] = &[
+    "Array", "BigInt", "Boolean", "Date", "Error", "Function", "JSON", "Map", "Number", "Object",
+    "Omit", "Partial", "Pick", "Promise", "Readonly", "Record", "RegExp", "Required", "Set",
+    "String", "Symbol",
+];
+
+/// Renders TypeScript declarations of the root type (named `root_name`) and an interface for
+/// every nested object, named after the property it is found at.
+#[must_use]
+pub fn render_typescript(schema: &SchemaHypothesis, root_name: &str) -> String {
+    let type_names = TypeNames::reserving(&schema.root, root_name, RESERVED);
+    let mut declarations = Vec::new();
+
+    if !schema.root.is_object() {
+        let mut declaration = String::new();
+        write_doc_comment(&mut declaration, &schema.root, "");
+        let _ = writeln!(
+            declaration,
+            "export type {} = {};",
+            type_names.root_name(),
+            type_of(&schema.root, &type_names)
+        );
+        declarations.push(declaration);
+    }
+    for (name, object) in type_names.objects() {
+        declarations.push(render_interface(name, object, &type_names));
+    }
+
+    declarations.join("\n")
+}
+
+fn render_interface(name: &str, object: &ObjectNode, type_names: &TypeNames) -> String {
+    let mut interface = format!("export interface {name} {{\n");
+    for (key, property) in &object.properties {
+        write_doc_comment(&mut interface, &property.node_type, "  ");
+        let optional = if property.required { "" } else { "?" };
+        let _ = writeln!(
+            interface,
+            "  {}{}: {};",
+            property_name(key),
+            optional,
+            type_of(&property.node_type, type_names)
+        );
+    }
+    interface.push_str("}\n");
+    interface
+}
+
+fn type_of(node_type: &NodeType, type_names: &TypeNames) -> String {
+    match node_type {
+        NodeType::String(_) | NodeType::Date(_) | NodeType::DateTime(_) => "string".to_string(),
+        NodeType::Integer(_) | NodeType::Number(_) => "number".to_string(),
+        NodeType::Boolean => "boolean".to_string(),
+        NodeType::Null => "null".to_string(),
+        NodeType::Array(array) => match &array.items {
+            Some(items) => {
+                let items = type_of(items, type_names);
+                if items.contains(' ') {
+                    format!("({items})[]")
+                } else {
+                    format!("{items}[]")
+                }
+            }
+            None => "unknown[]".to_string(),
+        },
+        NodeType::Object(object) => type_names.name(object).to_string(),
+        NodeType::Any(any) => {
+            let mut types: Vec<String> = Vec::new();
+            // `null` last, as in `string | null`
+            let (nulls, others): (Vec<_>, Vec<_>) = any
+                .nodes
+                .iter()
+                .partition(|node_type| **node_type == NodeType::Null);
+            for node_type in others.into_iter().chain(nulls) {
+                let node_type = type_of(node_type, type_names);
+                if !types.contains(&node_type) {
+                    types.push(node_type);
+                }
+            }
+            if types.is_empty() {
+                "unknown".to_string()
+            } else {
+                types.join(" | ")
+            }
+        }
+    }
+}
+
+/// Dates and date-times are strings in TypeScript, so their format is given in a doc comment.
+fn write_doc_comment(declaration: &mut String, node_type: &NodeType, indent: &str) {
+    let comment = match date_format(node_type) {
+        Some(NodeType::Date(_)) => "ISO 8601 date, e.g. `2022-01-31`",
+        Some(_) => "ISO 8601 date-time, e.g. `2022-01-31T12:00:00Z`",
+        None => return,
+    };
+    let _ = writeln!(declaration, "{indent}/** {comment} */");
+}
+
+fn date_format(node_type: &NodeType) -> Option<&NodeType> {
+    match node_type {
+        NodeType::Date(_) | NodeType::DateTime(_) => Some(node_type),
+        NodeType::Array(array) => array.items.as_deref().and_then(date_format),
+        NodeType::Any(any) => any.nodes.iter().find_map(date_format),
+        _ => None,
+    }
+}
+
+fn property_name(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        key.to_string()
+    } else {
+        serde_json::Value::from(key).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::renderer::typescript_renderer::render_typescript;
+    use crate::{generate_hypothesis, merge_hypothesis};
+
+    #[test]
+    fn test_interfaces() {
+        let hypothesis = merge_hypothesis(
+            generate_hypothesis(&json!({
+                "id": 1,
+                "created": "2022-01-01T00:00:00Z",
+                "customer": {"name": "x"},
+                "tags": [],
+                "content-type": "text",
+                "value": 1
+            })),
+            generate_hypothesis(&json!({
+                "id": 2,
+                "created": "2022-01-01T00:00:00Z",
+                "customer": {"name": "y"},
+                "tags": ["a", 1],
+                "value": null
+            })),
+        );
+
+        let actual = render_typescript(&hypothesis, "event");
+
+        assert_eq!(
+            actual,
+            r#"export interface Event {
+  "content-type"?: string;
+  /** ISO 8601 date-time, e.g. `2022-01-31T12:00:00Z` */
+  created: string;
+  customer: Customer;
+  id: number;
+  tags: (number | string)[];
+  value: number | null;
+}
+
+export interface Customer {
+  name: string;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_reserved_names() {
+        let hypothesis = generate_hypothesis(&json!({"date": {"day": 1}, "map": [{"id": 1}]}));
+
+        let actual = render_typescript(&hypothesis, "Root");
+
+        assert_eq!(
+            actual,
+            r#"export interface Root {
+  date: RootDate;
+  map: MapItem[];
+}
+
+export interface RootDate {
+  day: number;
+}
+
+export interface MapItem {
+  id: number;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_root_type() {
+        let hypothesis = generate_hypothesis(&json!([{"day": "2022-01-01"}, []]));
+
+        let actual = render_typescript(&hypothesis, "Root");
+
+        assert_eq!(
+            actual,
+            r#"export type Root = (unknown[] | RootItem)[];
+
+export interface RootItem {
+  /** ISO 8601 date, e.g. `2022-01-31` */
+  day: string;
+}
+"#
+        );
+    }
+}
@@ -3,15 +3,18 @@ use std::collections::BTreeSet;
 pub enum SetVariances<'a, T> {
     Empty,
     OneElement(&'a T),
+    // the set is only matched on, which newer compilers report as never read
+    #[allow(dead_code)]
     Multiple(&'a BTreeSet<T>),
 }
 
 impl<'a, T> SetVariances<'a, T> {
     pub fn new(s: &'a BTreeSet<T>) -> Self {
-        match s.len() {
-            0 => Self::Empty,
-            1 => Self::OneElement(s.iter().next().unwrap()),
-            _ => Self::Multiple(s),
+        let mut elements = s.iter();
+        match (elements.next(), elements.next()) {
+            (None, _) => Self::Empty,
+            (Some(element), None) => Self::OneElement(element),
+            (Some(_), Some(_)) => Self::Multiple(s),
         }
     }
 }
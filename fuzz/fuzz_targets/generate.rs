@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use schema2000::{generate_hypothesis_from_reader, render_schema};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(hypothesis) = generate_hypothesis_from_reader(data) {
+        let _ = render_schema(&hypothesis);
+    }
+});
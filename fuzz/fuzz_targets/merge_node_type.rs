@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use schema2000::model::NodeType;
+use schema2000::{merge_node_type, render_schema, SchemaHypothesis};
+
+// The node types are arbitrary, i.e. they need not be ones derived from documents (e.g. an
+// `AnyNode` with a single or nested alternatives), merging must not panic on them anyway.
+fuzz_target!(|node_types: (NodeType, NodeType)| {
+    let (a, b) = node_types;
+    let merged = merge_node_type(a, b);
+    let _ = render_schema(&SchemaHypothesis::new(merged));
+});
@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use schema2000::{generate_hypothesis, DocumentReader, InferredHypothesis};
+use serde_json::Value;
+
+// Reads arbitrary bytes line by line, both as values and as hypotheses derived while parsing;
+// both ways have to agree on every line.
+fuzz_target!(|data: &[u8]| {
+    let values = DocumentReader::<_, Value>::ndjson(data);
+    let hypotheses = DocumentReader::<_, InferredHypothesis>::ndjson(data);
+
+    for (value, hypothesis) in values.zip(hypotheses) {
+        match (value, hypothesis) {
+            (Ok(value), Ok(hypothesis)) => {
+                assert_eq!(value.offset, hypothesis.offset);
+                assert_eq!(generate_hypothesis(&value.value), hypothesis.value.0);
+            }
+            (Err(_), Err(_)) => {}
+            (value, hypothesis) => panic!("{:?} differs from {:?}", value, hypothesis),
+        }
+    }
+});
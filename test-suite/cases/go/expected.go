@@ -0,0 +1,28 @@
+package shop
+
+import "time"
+
+type Order struct {
+	DeliveredAt *time.Time `json:"deliveredAt,omitempty"`
+	Func        *string    `json:"func,omitempty"`
+	Größe       int64      `json:"größe"`
+	ID          int64      `json:"id"`
+	Meta        Meta       `json:"meta"`
+	Range       []any      `json:"range"`
+	Recipient   *Recipient `json:"recipient,omitempty"`
+	TrackingURL string     `json:"trackingUrl,omitempty"`
+	Type        *string    `json:"type"`
+	Weight      *float64   `json:"weight"`
+	X日本         string     `json:"日本,omitempty"`
+}
+
+type Meta struct {
+}
+
+type Recipient struct {
+	Map  Map    `json:"map"`
+	Name string `json:"name"`
+}
+
+type Map struct {
+}
@@ -0,0 +1,27 @@
+package com.example.shop;
+
+import com.fasterxml.jackson.annotation.JsonProperty;
+import com.fasterxml.jackson.databind.JsonNode;
+import java.time.OffsetDateTime;
+import java.util.List;
+
+public record Order(
+        @JsonProperty("String") String string,
+        Double balance,
+        @JsonProperty("class") String class_,
+        OffsetDateTime closedAt,
+        @JsonProperty("default") JsonNode default_,
+        Long größe,
+        Long id,
+        List<Double> limits,
+        Owner owner,
+        Settings settings) {
+
+    public record Owner(
+            String name,
+            @JsonProperty("public") Boolean public_) {
+    }
+
+    public record Settings() {
+    }
+}
@@ -0,0 +1,27 @@
+package com.example.shop
+
+import com.fasterxml.jackson.annotation.JsonProperty
+import com.fasterxml.jackson.databind.JsonNode
+import java.time.LocalDate
+import java.time.OffsetDateTime
+
+data class Order(
+    @JsonProperty("\$ref") val ref: String? = null,
+    val attributes: Attributes,
+    val größe: Long,
+    val id: Long,
+    val `object`: String? = null,
+    val price: Double?,
+    val supplier: Supplier? = null,
+    val tags: List<JsonNode>,
+    val updatedAt: OffsetDateTime? = null,
+    val `val`: String?,
+    val `when`: LocalDate,
+)
+
+class Attributes
+
+data class Supplier(
+    val `is`: Boolean,
+    val name: String,
+)
@@ -0,0 +1,31 @@
+import datetime as dt
+from typing import Optional, Union
+
+from pydantic import BaseModel, ConfigDict, Field
+
+
+class Meta(BaseModel):
+    pass
+
+
+class Passenger(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    none: Optional[bool] = Field(default=None, alias="None")
+    name: str
+
+
+class Order(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    class_: Optional[str] = Field(alias="class")
+    departsAt: Optional[dt.datetime] = None
+    from_: str = Field(alias="from")
+    größe: Union[int, float]
+    id: Union[int, str]
+    int_: Optional[int] = Field(alias="int")
+    meta: Meta
+    field_model_name: str = Field(alias="model_name")
+    passenger: Optional[Passenger]
+    seat: Optional[Union[int, str]]
+    str_: Optional[str] = Field(default=None, alias="str")
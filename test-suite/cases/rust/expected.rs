@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(rename = "2nd", default, skip_serializing_if = "Option::is_none")]
+    pub field_2nd: Option<String>,
+    #[serde(rename = "Option")]
+    pub option: EventOption,
+    pub amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub r#ref: EventRef,
+    #[serde(rename = "self")]
+    pub self_: EventSelf,
+    #[serde(rename = "status-code")]
+    pub status_code: EventStatusCode,
+    pub r#type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EventRef {
+    Integer(i64),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EventStatusCode {
+    Integer(i64),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventOption {
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventSelf {
+    pub href: String,
+}
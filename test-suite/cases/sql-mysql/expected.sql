@@ -0,0 +1,21 @@
+CREATE TABLE `order` (
+    `_id` BIGINT PRIMARY KEY,
+    `customer_group` BIGINT,
+    `customer_name` TEXT NOT NULL,
+    `größe` DOUBLE NOT NULL,
+    `id` JSON NOT NULL,
+    `meta` JSON NOT NULL,
+    `note_s` TEXT,
+    `select` TEXT,
+    `shipped_at` DATETIME(3)
+);
+
+CREATE TABLE `order_lines` (
+    `_id` BIGINT PRIMARY KEY,
+    `_parent_id` BIGINT NOT NULL,
+    `_position` BIGINT NOT NULL,
+    `attrs` JSON NOT NULL,
+    `qty` DOUBLE NOT NULL,
+    `sku` TEXT NOT NULL,
+    FOREIGN KEY (`_parent_id`) REFERENCES `order` (`_id`)
+);
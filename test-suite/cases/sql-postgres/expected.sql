@@ -0,0 +1,22 @@
+CREATE TABLE "order" (
+    "_id" BIGINT PRIMARY KEY,
+    "amount" DOUBLE PRECISION,
+    "billing_street" TEXT,
+    "billing_table" TEXT,
+    "größe" BIGINT NOT NULL,
+    "id" BIGINT NOT NULL,
+    "note" TEXT,
+    "order" JSONB,
+    "paid_at" TIMESTAMPTZ,
+    "payload" JSONB NOT NULL,
+    "user" TEXT
+);
+
+CREATE TABLE "order_items" (
+    "_id" BIGINT PRIMARY KEY,
+    "_parent_id" BIGINT NOT NULL,
+    "_position" BIGINT NOT NULL,
+    "extras" JSONB NOT NULL,
+    "sku" TEXT NOT NULL,
+    FOREIGN KEY ("_parent_id") REFERENCES "order" ("_id")
+);
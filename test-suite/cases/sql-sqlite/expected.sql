@@ -0,0 +1,22 @@
+CREATE TABLE "order" (
+    "_id" INTEGER PRIMARY KEY,
+    "day" TEXT,
+    "extra" TEXT NOT NULL,
+    "group" TEXT,
+    "größe" INTEGER NOT NULL,
+    "id" INTEGER NOT NULL,
+    "index" TEXT,
+    "place_limit" INTEGER,
+    "place_name" TEXT,
+    "rating" REAL,
+    "visited_at" TEXT
+);
+
+CREATE TABLE "order_photos" (
+    "_id" INTEGER PRIMARY KEY,
+    "_parent_id" INTEGER NOT NULL,
+    "_position" INTEGER NOT NULL,
+    "caption" TEXT,
+    "url" TEXT NOT NULL,
+    FOREIGN KEY ("_parent_id") REFERENCES "order" ("_id")
+);
@@ -0,0 +1,27 @@
+export interface Setting {
+  "2fa": boolean;
+  class: string;
+  "content-type": string;
+  date: SettingDate;
+  default: boolean;
+  delete?: boolean;
+  id: number;
+  map: SettingMap;
+  parent: Parent | null;
+  tags: (number | string)[];
+  value: number | string | null;
+}
+
+export interface SettingDate {
+  day: number;
+  month: number;
+}
+
+export interface SettingMap {
+  x: number;
+  y: number;
+}
+
+export interface Parent {
+  id: number;
+}
@@ -0,0 +1,105 @@
+use proptest::collection::{btree_map, vec};
+use proptest::prelude::*;
+use schema2000::model::NodeType;
+use schema2000::{
+    generate_hypothesis, merge_hypothesis, merge_node_type, render_json_schema,
+    render_json_schema_with_options, JsonSchemaOptions,
+};
+use serde_json::{json, Map, Value};
+
+fn arb_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z ]{0,8}",
+        Just("2000-01-01".to_string()),
+        Just("2024-02-29".to_string()),
+        Just("2000-02-30".to_string()),
+        Just("2000-01-01T00:00:00Z".to_string()),
+        Just("2000-01-01T00:00:00.123+02:00".to_string()),
+        Just("2000-13-01T00:00:00Z".to_string()),
+    ]
+}
+
+fn arb_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        (-1e6..1e6f64).prop_map(|n| json!(n)),
+        arb_string().prop_map(Value::from),
+    ];
+    leaf.prop_recursive(4, 64, 6, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..6).prop_map(Value::Array),
+            btree_map("[a-e]", inner, 0..6).prop_map(|properties| Value::Object(
+                properties.into_iter().collect::<Map<_, _>>()
+            )),
+        ]
+    })
+}
+
+/// The type of several merged documents, so that merging covers alternatives and objects with
+/// optional properties, too.
+fn arb_node_type() -> impl Strategy<Value = NodeType> {
+    vec(arb_json(), 1..4).prop_map(|documents| {
+        documents
+            .iter()
+            .map(|document| generate_hypothesis(document).root)
+            .reduce(merge_node_type)
+            .unwrap()
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_schema_accepts_all_documents(documents in vec(arb_json(), 1..8)) {
+        let hypothesis = documents
+            .iter()
+            .map(generate_hypothesis)
+            .reduce(merge_hypothesis)
+            .unwrap();
+        let open = render_json_schema(&hypothesis);
+        let closed = render_json_schema_with_options(
+            &hypothesis,
+            &JsonSchemaOptions {
+                closed: true,
+                ..JsonSchemaOptions::default()
+            },
+        );
+
+        for schema in [open, closed] {
+            let validator = jsonschema::options()
+                .should_validate_formats(true)
+                .build(&schema)
+                .unwrap();
+            for document in &documents {
+                prop_assert!(
+                    validator.is_valid(document),
+                    "{} does not accept {}",
+                    schema,
+                    document
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_is_commutative(a in arb_node_type(), b in arb_node_type()) {
+        prop_assert_eq!(
+            merge_node_type(a.clone(), b.clone()),
+            merge_node_type(b, a)
+        );
+    }
+
+    #[test]
+    fn test_merge_is_associative(
+        a in arb_node_type(),
+        b in arb_node_type(),
+        c in arb_node_type()
+    ) {
+        prop_assert_eq!(
+            merge_node_type(merge_node_type(a.clone(), b.clone()), c.clone()),
+            merge_node_type(a, merge_node_type(b, c))
+        );
+    }
+}